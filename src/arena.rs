@@ -0,0 +1,132 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::card::Deck;
+use crate::game::{Betting, Blinds, Game, GameError};
+use crate::player::{Observation, Player};
+
+/// The [`ArenaResult`] struct. The outcome of a match between players, in seat order.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArenaResult {
+    pub hands: usize,
+    /// The chips won or lost by each player.
+    pub winnings: Vec<i64>,
+    /// The win rate of each player, in big blinds per 100 hands.
+    pub bb_per_100: Vec<f64>,
+    /// The half width of the 95% confidence interval of each win rate, in big blinds per 100 hands.
+    pub confidence: Vec<f64>,
+}
+
+/// The [`Arena`] struct. A table where players play each other hand after hand, to measure their win rates.
+///
+/// Every hand starts with the players sitting with the same stack, the button moving by one seat each hand.
+/// The decks are shuffled by a random number generator seeded by the given seed, so a match can be replayed,
+/// and different players can be compared on the same cards.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Arena {
+    pub blinds: Blinds,
+    pub betting: Betting,
+    pub stack: u64,
+}
+
+impl Arena {
+    /// It plays the given number of hands between the players, returning their results.
+    ///
+    pub fn play(&self, players: &mut [Box<dyn Player>], hands: usize, seed: u64) -> Result<ArenaResult, GameError> {
+        let n = players.len();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut results: Vec<Vec<f64>> = vec![Vec::with_capacity(hands); n];
+        let mut winnings = vec![0i64; n];
+        for hand in 0..hands {
+            let mut cards = Deck::create_ordered_deck().remaining(&[]);
+            cards.shuffle(&mut rng);
+            let mut game = Game::new(&vec![self.stack; n], hand % n, self.blinds, self.betting, Deck::new(cards))?;
+            while let Some(observation) = Observation::of(&game) {
+                let action = players[observation.seat].act(&observation);
+                game.act(action)?;
+            }
+            for ((result, won), seat) in results.iter_mut().zip(winnings.iter_mut()).zip(&game.seats) {
+                let delta = seat.stack as i64 - self.stack as i64;
+                *won += delta;
+                result.push(delta as f64 / self.blinds.big as f64);
+            }
+        }
+
+        let (bb_per_100, confidence) = results.iter().map(|r| Self::rate(r)).unzip();
+        Ok(ArenaResult {
+            hands,
+            winnings,
+            bb_per_100,
+            confidence,
+        })
+    }
+
+    /// function that returns the mean of the results per hand in big blinds per 100 hands, with the half width of its 95% confidence interval
+    ///
+    fn rate(results: &[f64]) -> (f64, f64) {
+        let n = results.len() as f64;
+        if results.is_empty() {
+            return (0.0, 0.0);
+        }
+        let mean = results.iter().sum::<f64>() / n;
+        let variance = results.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+        (mean * 100.0, 1.96 * (variance / n).sqrt() * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::Arena;
+    use crate::game::{Betting, Blinds};
+    use crate::player::{CallingStation, Player, RandomPlayer, TightAggressive};
+
+    fn arena() -> Arena {
+        Arena {
+            blinds: Blinds {
+                small: 1,
+                big: 2,
+                ante: 0,
+            },
+            betting: Betting::NoLimit,
+            stack: 200,
+        }
+    }
+
+    #[test]
+    fn arena_replay() {
+        let play = |seed| {
+            let mut players: Vec<Box<dyn Player>> = vec![
+                Box::new(RandomPlayer::new(StdRng::seed_from_u64(1))),
+                Box::new(CallingStation),
+                Box::new(CallingStation),
+            ];
+            arena().play(&mut players, 200, seed).unwrap()
+        };
+        let result = play(42);
+        assert_eq!(result.hands, 200);
+        assert_eq!(result.winnings.iter().sum::<i64>(), 0);
+        assert!(result.confidence.iter().all(|c| *c > 0.0));
+        // the same seed deals the same cards
+        assert_eq!(play(42), result);
+        assert_ne!(play(7), result);
+    }
+
+    #[test]
+    fn arena_tight_aggressive() {
+        let mut tag = TightAggressive::new(StdRng::seed_from_u64(1));
+        tag.samples = 30;
+        let mut players: Vec<Box<dyn Player>> = vec![Box::new(tag), Box::new(RandomPlayer::new(StdRng::seed_from_u64(2)))];
+        let result = arena().play(&mut players, 200, 42).unwrap();
+        // the random player gives its chips away
+        assert!(result.bb_per_100[0] > 0.0, "{result:?}");
+        assert_eq!(result.bb_per_100[0], -result.bb_per_100[1]);
+        // 200 hands at a big blind of 2 chips
+        assert!((result.winnings[0] as f64 / 4.0 - result.bb_per_100[0]).abs() < 1e-9);
+    }
+}
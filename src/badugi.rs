@@ -0,0 +1,125 @@
+use std::cmp::Ordering;
+
+use crate::card::Card;
+
+/// The [`BadugiRank`] struct. It's the value of a Badugi hand: the number of cards playing, all of distinct suits and values, plus their values.
+///
+/// The kickers are the values of the playing cards in descending order, the Ace counting as 1, padded with zeros.
+/// More playing cards make a better hand, then the lower kickers. As for [`crate::match_evaluator::Rank`], a better [`BadugiRank`] compares as less.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BadugiRank {
+    pub size: u8,
+    pub kickers: [u8; 4],
+}
+
+impl PartialOrd for BadugiRank {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BadugiRank {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .size
+            .cmp(&self.size)
+            .then_with(|| self.kickers.cmp(&other.kickers))
+    }
+}
+
+impl BadugiRank {
+    /// It tells if the hand is a Badugi, i.e. four cards of different suits and values.
+    ///
+    pub fn is_badugi(&self) -> bool {
+        self.size == 4
+    }
+}
+
+/// The [`BadugiEvaluator`] struct. It evaluates Badugi hands of four cards.
+///
+/// i.e. A♣️ 2♦️ 3♥️ 4♠️ is the best possible hand, while K♣️ Q♣️ J♣️ 10♣️ only plays its 10 as a one-card hand.
+///
+pub struct BadugiEvaluator;
+
+impl BadugiEvaluator {
+    /// It evaluates the [BadugiRank] of four cards, keeping the best subset of cards of distinct suits and values.
+    ///
+    pub fn eval(cards: &[Card; 4]) -> BadugiRank {
+        // every non empty subset of the four cards, as a bit mask
+        (1..16u8)
+            .map(|mask| (0..4).filter(|i| mask & (1 << i) != 0).map(|i| &cards[i]).collect::<Vec<&Card>>())
+            .filter(|subset| Self::distinct(subset))
+            .map(|subset| Self::rank(&subset))
+            .min()
+            .unwrap()
+    }
+
+    /// function that ranks a subset of cards of distinct suits and values
+    ///
+    fn rank(cards: &[&Card]) -> BadugiRank {
+        let mut kickers = [0; 4];
+        for (k, card) in kickers.iter_mut().zip(cards) {
+            *k = if card.val == 14 { 1 } else { card.val };
+        }
+        kickers.sort_by(|a, b| b.cmp(a));
+        BadugiRank {
+            size: cards.len() as u8,
+            kickers,
+        }
+    }
+
+    /// function that checks if the cards are all of distinct suits and values
+    ///
+    fn distinct(cards: &[&Card]) -> bool {
+        (1..cards.len()).all(|i| {
+            cards[..i]
+                .iter()
+                .all(|c| c.suit != cards[i].suit && c.val != cards[i].val)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BadugiEvaluator;
+    use crate::card::Card;
+    use crate::newcard;
+
+    fn eval(cards: [&str; 4]) -> super::BadugiRank {
+        BadugiEvaluator::eval(&cards.map(|c| newcard![c]))
+    }
+
+    #[test]
+    fn badugi_ranks() {
+        let best = eval(["Ac", "2d", "3h", "4s"]);
+        assert!(best.is_badugi());
+        assert_eq!(best.kickers, [4, 3, 2, 1]);
+
+        // a single suit plays a single card, the lowest one
+        let one = eval(["Kc", "Qc", "Jc", "10c"]);
+        assert_eq!(one.size, 1);
+        assert_eq!(one.kickers, [10, 0, 0, 0]);
+
+        // the deuces and the clubs play once, so the deuce of clubs is dropped
+        let three = eval(["2c", "2d", "5h", "9c"]);
+        assert_eq!(three.size, 3);
+        assert_eq!(three.kickers, [9, 5, 2, 0]);
+
+        let three = eval(["Ac", "Kc", "5h", "7d"]);
+        assert_eq!(three.kickers, [7, 5, 1, 0]);
+
+        // four of a kind plays a single card
+        assert_eq!(eval(["10c", "10d", "10h", "10s"]).kickers, [10, 0, 0, 0]);
+    }
+
+    #[test]
+    fn badugi_comparison() {
+        // any badugi beats any three-card hand
+        assert!(eval(["Kc", "Qd", "Jh", "10s"]) < eval(["Ac", "2d", "3h", "3s"]));
+        assert!(eval(["Ac", "2d", "3h", "4s"]) < eval(["Ac", "2d", "3h", "5s"]));
+        assert!(eval(["Ac", "2d", "3h", "5s"]) < eval(["Ac", "2d", "4h", "5s"]));
+        assert!(eval(["2c", "3d", "4h", "4c"]) < eval(["Ac", "2c", "Kh", "Ks"]));
+        assert_eq!(eval(["Ac", "2d", "3h", "4s"]), eval(["As", "2h", "3c", "4d"]));
+    }
+}
@@ -5,6 +5,7 @@ use pokerface::{
 
 static VERSION: &str = env!("CARGO_PKG_VERSION");
 
+#[allow(clippy::single_match)]
 fn main() {
     println!("Poker Face {} - 🦀 for ♠️ ♣️ ♥️ ♦️", VERSION);
     println!("Prehashing cards...");
@@ -21,12 +21,40 @@ pub enum Suit {
     Clubs,
 }
 
+impl Suit {
+    /// It returns the position of the [Suit] in the ranking used to break ties, i.e. for the bring-in of Stud games:
+    /// [Suit::Clubs] < [Suit::Diamonds] < [Suit::Hearts] < [Suit::Spades].
+    ///
+    pub const fn order(&self) -> u8 {
+        match self {
+            Suit::Clubs => 0,
+            Suit::Diamonds => 1,
+            Suit::Hearts => 2,
+            Suit::Spades => 3,
+        }
+    }
+}
+
+impl PartialOrd for Suit {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Suit {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.order().cmp(&other.order())
+    }
+}
+
 /// The [`Card`] struct. It represents a card, composed by a [`Suit`] enum and a rank value.
 ///
 /// The range of rank values is described as follows:
 ///  A,  K,  Q,  J, 10, 9, 8, 7, 6, 5, 4, 3, 2
 /// 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2
 ///
+/// A joker has value 0, and a suit only telling it apart from the other joker of the deck.
+///
 #[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
 pub struct Card {
     pub val: u8,
@@ -37,11 +65,23 @@ impl Card {
     pub fn new(val: u8, suit: Suit) -> Self {
         Card { suit, val }
     }
+
+    /// It creates a joker. A deck with two jokers has a black one ([Suit::Spades]) and a red one ([Suit::Hearts]).
+    ///
+    pub fn joker(suit: Suit) -> Self {
+        Card { suit, val: 0 }
+    }
+
+    /// It tells if the card is a joker.
+    ///
+    pub fn is_joker(&self) -> bool {
+        self.val == 0
+    }
 }
 
 impl PartialOrd for Card {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.val.partial_cmp(&other.val)
+        Some(self.cmp(other))
     }
 }
 
@@ -106,6 +146,9 @@ impl Display for Card {
     
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, " ")?;
+        if self.is_joker() {
+            return write!(f, "🃏 ");
+        }
         match self.val {
             n @ 2..=10 => write!(f, "{n}"),
             14 => write!(f, "A"),
@@ -135,6 +178,7 @@ pub struct Hand {
 impl Hand {
     /// sorting the hand in a descenting order
     ///
+    #[allow(clippy::unused_unit)]
     pub fn sort(&mut self) -> () {
         self.hand.sort();
         self.hand.reverse();
@@ -162,29 +206,96 @@ pub struct Deck {
 }
 
 impl Deck {
+    /// It creates an unshuffled deck, with the cards ordered by suit and value.
+    ///
+    pub fn create_ordered_deck() -> Deck {
+        Self::ordered(2)
+    }
+
     /// It creates a shuffled deck, ready to play.
     ///
     pub fn create_shuffled_deck() -> Deck {
+        Self::shuffled(Self::create_ordered_deck())
+    }
+
+    /// It creates an unshuffled deck of 52 cards plus 1 or 2 jokers, the black one first.
+    ///
+    pub fn create_ordered_deck_with_jokers(jokers: usize) -> Deck {
+        let mut deck = Self::create_ordered_deck();
+        deck.deck
+            .extend([Card::joker(Suit::Spades), Card::joker(Suit::Hearts)].iter().take(jokers));
+        deck
+    }
+
+    /// It creates a shuffled deck of 52 cards plus 1 or 2 jokers, ready to play.
+    ///
+    pub fn create_shuffled_deck_with_jokers(jokers: usize) -> Deck {
+        Self::shuffled(Self::create_ordered_deck_with_jokers(jokers))
+    }
+
+    /// It creates an unshuffled short deck of 36 cards, from 6 to Ace, for Short-deck (6+) Hold'em.
+    ///
+    pub fn create_ordered_short_deck() -> Deck {
+        Self::ordered(6)
+    }
+
+    /// It creates a shuffled short deck of 36 cards, from 6 to Ace, ready to play Short-deck (6+) Hold'em.
+    ///
+    pub fn create_shuffled_short_deck() -> Deck {
+        Self::shuffled(Self::create_ordered_short_deck())
+    }
+
+    /// function that creates a deck ordered by suit and value, starting from the given lowest value
+    ///
+    fn ordered(lowest: u8) -> Deck {
         let mut deck: Vec<Card> = Vec::new();
 
         for suit in Suit::iter() {
-            for val in 2..=14 {
+            for val in lowest..=14 {
                 deck.push(Card::new(val, suit));
             }
         }
+        Deck { deck, it: 0 }
+    }
+
+    /// function that shuffles a deck
+    ///
+    fn shuffled(mut deck: Deck) -> Deck {
         let mut rng = thread_rng();
-        deck.shuffle(&mut rng);
+        deck.deck.shuffle(&mut rng);
+        deck
+    }
+
+    /// An handy constructor for tests, with the cards to be dealt in the given order.
+    ///
+    pub fn new(deck: Vec<Card>) -> Deck {
         Deck { deck, it: 0 }
     }
 
+    /// It deals a single [Card] from the deck.
+    ///
+    pub fn deal(&mut self) -> Option<Card> {
+        let card = self.deck.get(self.it).copied()?;
+        self.it += 1;
+        Some(card)
+    }
+
+    /// It lists the cards still in the deck, skipping the given dead cards (i.e. cards already known to be in play).
+    ///
+    pub fn remaining(&self, dead: &[Card]) -> Vec<Card> {
+        self.deck[self.it..]
+            .iter()
+            .filter(|c| !dead.contains(c))
+            .copied()
+            .collect()
+    }
+
     /// It gets a [Hand] of 5 cards from the deck.
     ///
     pub fn hand(&mut self) -> Option<Hand> {
-        if self.it == 52 {
-            return None; // deck is finisced!
-        }
+        // None when the deck is finisced!
         let hand: Hand = Hand {
-            hand: self.deck[self.it..=(self.it + 4)].try_into().ok()?,
+            hand: self.deck.get(self.it..self.it + 5)?.try_into().ok()?,
         };
         self.it += 5; // got 5 cards from the deck
         Some(hand)
@@ -192,6 +303,7 @@ impl Deck {
 }
 
 impl Display for Hand {
+    #[allow(clippy::writeln_empty_string)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for i in 0..=4 {
             write!(f, "{} ", self.hand[i])?;
@@ -201,9 +313,10 @@ impl Display for Hand {
 }
 
 impl Display for Deck {
+    #[allow(clippy::writeln_empty_string)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in 0..52 {
-            write!(f, "{} ", self.deck[i])?;
+        for (i, card) in self.deck.iter().enumerate() {
+            write!(f, "{} ", card)?;
             if (i + 1) % 13 == 0 {
                 writeln!(f, "")?;
             }
@@ -235,6 +348,13 @@ macro_rules! hand {
     };
 }
 
+#[macro_export]
+macro_rules! cards {
+    ($($c:expr),* $(,)?) => {
+        [$(newcard![$c]),*]
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,6 +373,42 @@ mod tests {
         assert_eq!(newcard!["9h"], Card::new(9, Suit::Hearts));
     }
 
+    #[test]
+    fn test_suit_order() {
+        assert!(Suit::Clubs < Suit::Diamonds);
+        assert!(Suit::Diamonds < Suit::Hearts);
+        assert!(Suit::Hearts < Suit::Spades);
+        assert_eq!(Suit::iter().max(), Some(Suit::Spades));
+    }
+
+    #[test]
+    fn test_short_deck() {
+        let deck = Deck::create_shuffled_short_deck();
+        let cards = deck.remaining(&[]);
+        assert_eq!(cards.len(), 36);
+        assert!(cards.iter().all(|c| c.val >= 6));
+        assert_eq!(Deck::create_shuffled_deck().remaining(&[]).len(), 52);
+    }
+
+    #[test]
+    fn test_jokers() {
+        let cards = Deck::create_shuffled_deck_with_jokers(2).remaining(&[]);
+        assert_eq!(cards.len(), 54);
+        assert_eq!(cards.iter().filter(|c| c.is_joker()).count(), 2);
+        assert!(cards.contains(&Card::joker(Suit::Hearts)));
+        assert_eq!(Deck::create_ordered_deck_with_jokers(1).remaining(&[]).len(), 53);
+        assert!(!newcard!["Ah"].is_joker());
+    }
+
+    #[test]
+    fn test_deal() {
+        let mut deck = Deck::new(vec![newcard!["Ad"], newcard!["2c"]]);
+        assert_eq!(deck.deal(), Some(newcard!["Ad"]));
+        assert_eq!(deck.remaining(&[]), vec![newcard!["2c"]]);
+        assert_eq!(deck.deal(), Some(newcard!["2c"]));
+        assert_eq!(deck.deal(), None);
+    }
+
     #[test]
     fn test_try_from_valid_hand() {
         let hand = hand!["Ad", "Kd", "Qd", "Jd", "10d"];
@@ -0,0 +1,150 @@
+use std::cmp::Ordering;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::card::{Card, Deck, Hand};
+use crate::match_evaluator::{MatchHandEvaluator, Rank, Score};
+
+/// The [`CaribbeanStud`] struct. The rules of Caribbean Stud poker, a five-card stud game against the dealer, with the pays of the raise.
+///
+/// The player bets the Ante, looks at the five cards and either folds, losing the Ante, or raises twice the Ante.
+/// The dealer, who shows one card, qualifies with Ace King or better: otherwise the Ante pays even money and the raise pushes.
+/// When the dealer qualifies, the best hand wins both bets, the raise being paid according to the paytable.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct CaribbeanStud {
+    pub raise_pays: Vec<(Rank, u32)>,
+}
+
+impl CaribbeanStud {
+    /// The common paytable: Royal Flush 100, Straight Flush 50, Four of a Kind 20, Full House 7, Flush 5, Straight 4, Three of a Kind 3,
+    /// Two Pair 2, anything else 1.
+    ///
+    pub fn standard() -> CaribbeanStud {
+        CaribbeanStud {
+            raise_pays: vec![
+                (Rank::RoyalFlush, 100),
+                (Rank::StraightFlush, 50),
+                (Rank::FourOfAKind, 20),
+                (Rank::FullHouse, 7),
+                (Rank::Flush, 5),
+                (Rank::Straight, 4),
+                (Rank::ThreeOfAKind, 3),
+                (Rank::TwoPair, 2),
+            ],
+        }
+    }
+
+    /// It tells if the dealer's hand qualifies, i.e. it's Ace King high or better.
+    ///
+    pub fn qualifies(dealer: &Score) -> bool {
+        !matches!(dealer.rank, Rank::HighCard(_)) || dealer.kickers[..2] == [14, 13]
+    }
+
+    /// A basic strategy: raise with a pair or better, or with Ace King holding a Queen or a Jack too,
+    /// or matching the dealer's up card. Otherwise fold.
+    ///
+    pub fn basic_strategy(player: &Hand, up: &Card) -> bool {
+        let score = MatchHandEvaluator::score(&mut player.clone());
+        if score.rank.position() <= Rank::OnePair.position() {
+            return true;
+        }
+        score.kickers[..2] == [14, 13]
+            && (score.kickers[2] >= 11 || player.get_hand_slice().iter().any(|c| c.val == up.val))
+    }
+
+    /// It settles the Ante (one unit) and the raise (two units) of the player, returning the net win in units.
+    ///
+    pub fn settle(&self, player: &Hand, dealer: &Hand, raise: bool) -> i64 {
+        if !raise {
+            return -1;
+        }
+        let player = MatchHandEvaluator::score(&mut player.clone());
+        let dealer = MatchHandEvaluator::score(&mut dealer.clone());
+        if !Self::qualifies(&dealer) {
+            return 1;
+        }
+        match player.cmp(&dealer) {
+            Ordering::Less => 1 + 2 * i64::from(self.raise_pay(&player.rank)),
+            Ordering::Equal => 0,
+            Ordering::Greater => -3,
+        }
+    }
+
+    /// It returns the pay of the raise for a winning [Rank].
+    ///
+    pub fn raise_pay(&self, rank: &Rank) -> u32 {
+        self.raise_pays
+            .iter()
+            .find(|(r, _)| r.position() == rank.position())
+            .map_or(1, |(_, pay)| *pay)
+    }
+
+    /// It estimates the house edge, as the player's expected loss per unit of Ante, playing `hands` random hands with the given strategy,
+    /// which decides whether to raise given the player's hand and the dealer's up card.
+    ///
+    pub fn house_edge<R: Rng>(&self, hands: usize, rng: &mut R, strategy: impl Fn(&Hand, &Card) -> bool) -> f64 {
+        let mut cards = Deck::create_ordered_deck().remaining(&[]);
+        let total: i64 = (0..hands)
+            .map(|_| {
+                let (dealt, _) = cards.partial_shuffle(rng, 10);
+                let player = Hand::new(dealt[..5].try_into().unwrap());
+                let dealer = Hand::new(dealt[5..].try_into().unwrap());
+                let raise = strategy(&player, &dealer.get_hand_slice()[0]);
+                self.settle(&player, &dealer, raise)
+            })
+            .sum();
+        -total as f64 / hands as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::CaribbeanStud;
+    use crate::card::{Card, Hand};
+    use crate::match_evaluator::{MatchHandEvaluator, Rank};
+    use crate::{hand, newcard};
+
+    #[test]
+    fn caribbean_qualifies() {
+        let score = |mut hand: Hand| MatchHandEvaluator::score(&mut hand);
+        assert!(CaribbeanStud::qualifies(&score(hand!["Ah", "Kd", "7c", "5s", "2h"])));
+        assert!(!CaribbeanStud::qualifies(&score(hand!["Ah", "Qd", "Jc", "5s", "2h"])));
+        assert!(CaribbeanStud::qualifies(&score(hand!["2h", "2d", "7c", "5s", "3h"])));
+    }
+
+    #[test]
+    fn caribbean_settlement() {
+        let game = CaribbeanStud::standard();
+        let flush = hand!["Kh", "Jh", "8h", "5h", "3h"];
+        let pair = hand!["Qc", "Qd", "9s", "7c", "4d"];
+        let no_qualify = hand!["Ad", "Qs", "9c", "7s", "4c"];
+
+        assert_eq!(game.settle(&flush, &pair, false), -1);
+        assert_eq!(game.settle(&flush, &pair, true), 1 + 2 * 5);
+        assert_eq!(game.settle(&pair, &flush, true), -3);
+        assert_eq!(game.settle(&pair, &no_qualify, true), 1);
+        assert_eq!(game.settle(&pair, &hand!["Qh", "Qs", "9d", "7s", "4c"], true), 0);
+        assert_eq!(game.raise_pay(&Rank::HighCard(newcard!["Ah"])), 1);
+        assert_eq!(game.raise_pay(&Rank::RoyalFlush), 100);
+    }
+
+    #[test]
+    fn caribbean_strategy_and_edge() {
+        let up = newcard!["9c"];
+        assert!(CaribbeanStud::basic_strategy(&hand!["2h", "2d", "7c", "5s", "3h"], &up));
+        assert!(CaribbeanStud::basic_strategy(&hand!["Ah", "Kd", "Jc", "5s", "3h"], &up));
+        assert!(CaribbeanStud::basic_strategy(&hand!["Ah", "Kd", "9h", "5s", "3h"], &up));
+        assert!(!CaribbeanStud::basic_strategy(&hand!["Ah", "Kd", "8c", "5s", "3h"], &up));
+        assert!(!CaribbeanStud::basic_strategy(&hand!["Ah", "Qd", "Jc", "5s", "3h"], &up));
+
+        // the house edge of the optimal strategy is 5.22%, about 5.36% for the basic one: a million hands estimate it within 0.5%
+        let mut rng = StdRng::seed_from_u64(42);
+        let edge = CaribbeanStud::standard().house_edge(1_000_000, &mut rng, CaribbeanStud::basic_strategy);
+        assert!((edge - 0.0536).abs() < 0.005, "{edge}");
+    }
+}
@@ -0,0 +1,593 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use crate::card::{Card, Deck, Suit};
+
+/// The [`Node`] enum. Who moves at a state of a [`GameTree`].
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Node {
+    /// Chance deals a card, or anything else out of the players' hands.
+    Chance,
+    /// The player of the given index, 0 or 1, is to act.
+    Player(usize),
+    /// The game is over.
+    Terminal,
+}
+
+/// The [`GameTree`] trait. A two-player zero-sum game of imperfect information, as the tree of its states.
+///
+/// The players don't see the whole state: what a player knows when acting is the information set,
+/// the states a player can't tell apart sharing the same key.
+///
+pub trait GameTree {
+    type State: Clone;
+    type Action: Copy + PartialEq + Debug;
+
+    /// It returns the state the game starts from.
+    ///
+    fn root(&self) -> Self::State;
+
+    /// It tells who moves at the given state.
+    ///
+    fn node(&self, state: &Self::State) -> Node;
+
+    /// It returns the states chance can lead to from a [Node::Chance] state, with their probabilities.
+    ///
+    fn outcomes(&self, state: &Self::State) -> Vec<(Self::State, f64)>;
+
+    /// It returns the actions of the player to act, always in the same order for the same information set.
+    ///
+    fn actions(&self, state: &Self::State) -> Vec<Self::Action>;
+
+    /// It returns the state after the player to act makes the given action.
+    ///
+    fn play(&self, state: &Self::State, action: Self::Action) -> Self::State;
+
+    /// It returns the utility of the given player at a [Node::Terminal] state, the other player getting the opposite.
+    ///
+    fn payoff(&self, state: &Self::State, player: usize) -> f64;
+
+    /// It returns the key of the information set of the player to act.
+    ///
+    fn info_set(&self, state: &Self::State) -> String;
+}
+
+/// The [`Strategy`] struct. The probability of each action in each information set, for both players.
+///
+/// The information sets missing are played uniformly at random.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Strategy<A> {
+    pub info_sets: HashMap<String, Vec<(A, f64)>>,
+}
+
+impl<A: Copy + PartialEq + Debug> Strategy<A> {
+    /// It returns the probability of the given action in an information set, if the information set is known.
+    ///
+    pub fn probability(&self, info_set: &str, action: A) -> Option<f64> {
+        self.info_sets
+            .get(info_set)?
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, p)| *p)
+    }
+
+    /// It returns the expected utility of the first player when both players play this strategy.
+    ///
+    pub fn value<G: GameTree<Action = A>>(&self, game: &G) -> f64 {
+        self.walk(game, &game.root())
+    }
+
+    /// It returns the exploitability of the strategy, i.e. the average of what each player would gain with a best response
+    /// against the other one playing this strategy. It's 0 at a Nash equilibrium.
+    ///
+    pub fn exploitability<G: GameTree<Action = A>>(&self, game: &G) -> f64 {
+        (self.best_response(game, 0) + self.best_response(game, 1)) / 2.0
+    }
+
+    /// It returns the expected utility of the given player playing the best response against this strategy.
+    ///
+    pub fn best_response<G: GameTree<Action = A>>(&self, game: &G, player: usize) -> f64 {
+        // the states of each information set of the best responder, with the chance of the opponent and of chance to reach them
+        let mut states: HashMap<String, Vec<(G::State, f64)>> = HashMap::new();
+        self.collect(game, &game.root(), player, 1.0, &mut states);
+        let mut decisions = HashMap::new();
+        self.respond(game, &game.root(), player, &states, &mut decisions)
+    }
+
+    /// function that returns the probabilities of the actions at a state, uniform if the information set is unknown
+    ///
+    fn probabilities<G: GameTree<Action = A>>(&self, game: &G, state: &G::State) -> Vec<(A, f64)> {
+        self.info_sets.get(&game.info_set(state)).cloned().unwrap_or_else(|| {
+            let actions = game.actions(state);
+            let p = 1.0 / actions.len() as f64;
+            actions.into_iter().map(|a| (a, p)).collect()
+        })
+    }
+
+    /// function that returns the expected utility of the first player from a state on
+    ///
+    fn walk<G: GameTree<Action = A>>(&self, game: &G, state: &G::State) -> f64 {
+        match game.node(state) {
+            Node::Terminal => game.payoff(state, 0),
+            Node::Chance => game.outcomes(state).iter().map(|(s, p)| p * self.walk(game, s)).sum(),
+            Node::Player(_) => self
+                .probabilities(game, state)
+                .iter()
+                .map(|(a, p)| p * self.walk(game, &game.play(state, *a)))
+                .sum(),
+        }
+    }
+
+    /// function that gathers the states of the information sets of the best responder, weighted by the chance to reach them
+    ///
+    fn collect<G: GameTree<Action = A>>(
+        &self,
+        game: &G,
+        state: &G::State,
+        player: usize,
+        reach: f64,
+        states: &mut HashMap<String, Vec<(G::State, f64)>>,
+    ) {
+        match game.node(state) {
+            Node::Terminal => {}
+            Node::Chance => {
+                for (s, p) in game.outcomes(state) {
+                    self.collect(game, &s, player, reach * p, states);
+                }
+            }
+            Node::Player(p) if p == player => {
+                states.entry(game.info_set(state)).or_default().push((state.clone(), reach));
+                for a in game.actions(state) {
+                    self.collect(game, &game.play(state, a), player, reach, states);
+                }
+            }
+            Node::Player(_) => {
+                for (a, p) in self.probabilities(game, state) {
+                    self.collect(game, &game.play(state, a), player, reach * p, states);
+                }
+            }
+        }
+    }
+
+    /// function that returns the utility of the best responder from a state on, choosing the best action of each information set
+    /// against all of its states, the deeper information sets first
+    ///
+    fn respond<G: GameTree<Action = A>>(
+        &self,
+        game: &G,
+        state: &G::State,
+        player: usize,
+        states: &HashMap<String, Vec<(G::State, f64)>>,
+        decisions: &mut HashMap<String, A>,
+    ) -> f64 {
+        match game.node(state) {
+            Node::Terminal => game.payoff(state, player),
+            Node::Chance => game
+                .outcomes(state)
+                .iter()
+                .map(|(s, p)| p * self.respond(game, s, player, states, decisions))
+                .sum(),
+            Node::Player(p) if p == player => {
+                let key = game.info_set(state);
+                let action = match decisions.get(&key) {
+                    Some(action) => *action,
+                    None => {
+                        let mut best = (f64::NEG_INFINITY, None);
+                        for a in game.actions(state) {
+                            let value: f64 = states[&key]
+                                .iter()
+                                .map(|(s, reach)| reach * self.respond(game, &game.play(s, a), player, states, decisions))
+                                .sum();
+                            if value > best.0 {
+                                best = (value, Some(a));
+                            }
+                        }
+                        let action = best.1.unwrap();
+                        decisions.insert(key, action);
+                        action
+                    }
+                };
+                self.respond(game, &game.play(state, action), player, states, decisions)
+            }
+            Node::Player(_) => self
+                .probabilities(game, state)
+                .iter()
+                .map(|(a, p)| p * self.respond(game, &game.play(state, *a), player, states, decisions))
+                .sum(),
+        }
+    }
+}
+
+/// function that returns the current strategy of an information set by regret matching: the actions with positive regret,
+/// in proportion to it, or any action uniformly when there's none
+///
+fn regret_matching(regrets: &[f64]) -> Vec<f64> {
+    let positive: f64 = regrets.iter().map(|r| r.max(0.0)).sum();
+    if positive > 0.0 {
+        regrets.iter().map(|r| r.max(0.0) / positive).collect()
+    } else {
+        vec![1.0 / regrets.len() as f64; regrets.len()]
+    }
+}
+
+/// The [`InfoSet`] struct. What the solver learns about an information set: the actions, their cumulative regrets and the cumulative strategy.
+///
+#[derive(Debug, Clone)]
+struct InfoSet<A> {
+    actions: Vec<A>,
+    regrets: Vec<f64>,
+    strategies: Vec<f64>,
+}
+
+/// The [`Cfr`] struct. A solver finding an approximate Nash equilibrium of a [`GameTree`] by counterfactual regret minimization.
+///
+/// Each iteration walks the whole tree once per player, updating the regrets of the player's information sets, and the average
+/// of the strategies played converges to an equilibrium. With CFR+ the cumulative regrets are floored at zero and the later
+/// iterations weigh more in the average, converging much faster.
+///
+pub struct Cfr<G: GameTree> {
+    pub game: G,
+    pub plus: bool,
+    pub iterations: usize,
+    info_sets: HashMap<String, InfoSet<G::Action>>,
+}
+
+impl<G: GameTree> Cfr<G> {
+    /// It creates a vanilla CFR solver for the game.
+    ///
+    pub fn new(game: G) -> Cfr<G> {
+        Cfr {
+            game,
+            plus: false,
+            iterations: 0,
+            info_sets: HashMap::new(),
+        }
+    }
+
+    /// It creates a CFR+ solver for the game.
+    ///
+    pub fn plus(game: G) -> Cfr<G> {
+        Cfr { plus: true, ..Self::new(game) }
+    }
+
+    /// It runs the given number of iterations.
+    ///
+    pub fn train(&mut self, iterations: usize) {
+        for _ in 0..iterations {
+            self.iterations += 1;
+            for player in 0..2 {
+                let root = self.game.root();
+                self.cfr(&root, player, [1.0, 1.0], 1.0);
+            }
+        }
+    }
+
+    /// It returns the average [Strategy] played so far, the approximate equilibrium.
+    ///
+    pub fn strategy(&self) -> Strategy<G::Action> {
+        let info_sets = self
+            .info_sets
+            .iter()
+            .map(|(key, info_set)| {
+                let total: f64 = info_set.strategies.iter().sum();
+                let n = info_set.actions.len() as f64;
+                let probabilities = info_set
+                    .actions
+                    .iter()
+                    .zip(&info_set.strategies)
+                    .map(|(a, s)| (*a, if total > 0.0 { s / total } else { 1.0 / n }))
+                    .collect();
+                (key.clone(), probabilities)
+            })
+            .collect();
+        Strategy { info_sets }
+    }
+
+    /// function that walks the tree updating the regrets of the player, returning the player's expected utility from the state on,
+    /// given the chance of each player and of chance to reach it
+    ///
+    fn cfr(&mut self, state: &G::State, player: usize, reach: [f64; 2], chance: f64) -> f64 {
+        match self.game.node(state) {
+            Node::Terminal => self.game.payoff(state, player),
+            Node::Chance => self
+                .game
+                .outcomes(state)
+                .iter()
+                .map(|(s, p)| p * self.cfr(s, player, reach, chance * p))
+                .sum(),
+            Node::Player(acting) => {
+                let key = self.game.info_set(state);
+                let actions = self.game.actions(state);
+                let strategy = {
+                    let info_set = self.info_sets.entry(key.clone()).or_insert_with(|| InfoSet {
+                        regrets: vec![0.0; actions.len()],
+                        strategies: vec![0.0; actions.len()],
+                        actions: actions.clone(),
+                    });
+                    regret_matching(&info_set.regrets)
+                };
+
+                let mut values = vec![0.0; actions.len()];
+                let mut value = 0.0;
+                for (a, action) in actions.iter().enumerate() {
+                    let mut reach = reach;
+                    reach[acting] *= strategy[a];
+                    values[a] = self.cfr(&self.game.play(state, *action), player, reach, chance);
+                    value += strategy[a] * values[a];
+                }
+
+                if acting == player {
+                    let weight = if self.plus { self.iterations as f64 } else { 1.0 };
+                    let info_set = self.info_sets.get_mut(&key).unwrap();
+                    for a in 0..actions.len() {
+                        info_set.regrets[a] += reach[1 - player] * chance * (values[a] - value);
+                        if self.plus {
+                            info_set.regrets[a] = info_set.regrets[a].max(0.0);
+                        }
+                        info_set.strategies[a] += weight * reach[player] * strategy[a];
+                    }
+                }
+                value
+            }
+        }
+    }
+}
+
+/// The [`Move`] enum. The actions of Kuhn and Leduc poker, where bets have a fixed size.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Move {
+    Fold,
+    Check,
+    Call,
+    Bet,
+    Raise,
+}
+
+/// The [`SmallPokerState`] struct. A state of a [`SmallPoker`] game.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct SmallPokerState {
+    /// The private card of each player, as dealt.
+    pub hole: Vec<Card>,
+    pub board: Vec<Card>,
+    /// The moves of each betting round so far.
+    pub rounds: Vec<Vec<Move>>,
+    pub contributed: [f64; 2],
+    pub folded: Option<usize>,
+}
+
+/// The [`SmallPoker`] struct. The small poker games used to study equilibria: Kuhn and Leduc poker.
+///
+/// Each player antes one chip and gets a private card from a reduced deck. In every betting round the first player acts first,
+/// the bets have the fixed size of the round, and at most `max_bets` bets and raises are allowed. Before each round after the first,
+/// a board card is dealt. At showdown a player pairing the board wins, otherwise the higher card.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct SmallPoker {
+    pub deck: Deck,
+    pub bet_sizes: Vec<f64>,
+    pub max_bets: usize,
+}
+
+impl SmallPoker {
+    /// Kuhn poker: a deck of Jack, Queen and King, a single betting round with bets of one chip and no raises.
+    ///
+    pub fn kuhn() -> SmallPoker {
+        SmallPoker {
+            deck: Deck::new([11, 12, 13].map(|v| Card::new(v, Suit::Spades)).to_vec()),
+            bet_sizes: vec![1.0],
+            max_bets: 1,
+        }
+    }
+
+    /// Leduc poker: a deck of two Jacks, Queens and Kings, two betting rounds with bets of 2 and 4 chips, a bet and a raise at most each.
+    ///
+    pub fn leduc() -> SmallPoker {
+        let cards = [Suit::Spades, Suit::Hearts]
+            .iter()
+            .flat_map(|suit| [11, 12, 13].map(|v| Card::new(v, *suit)))
+            .collect();
+        SmallPoker {
+            deck: Deck::new(cards),
+            bet_sizes: vec![2.0, 4.0],
+            max_bets: 2,
+        }
+    }
+
+    /// function that tells if the last betting round is over
+    ///
+    fn round_over(state: &SmallPokerState) -> bool {
+        let round = state.rounds.last().unwrap();
+        matches!(round[..], [.., Move::Call] | [Move::Check, Move::Check])
+    }
+
+    /// function that returns the strength of a player's hand at showdown, pairing the board first
+    ///
+    fn strength(&self, state: &SmallPokerState, player: usize) -> (bool, u8) {
+        let card = state.hole[player];
+        (state.board.iter().any(|b| b.val == card.val), card.val)
+    }
+}
+
+impl GameTree for SmallPoker {
+    type State = SmallPokerState;
+    type Action = Move;
+
+    fn root(&self) -> SmallPokerState {
+        SmallPokerState {
+            hole: vec![],
+            board: vec![],
+            rounds: vec![vec![]],
+            contributed: [1.0, 1.0],
+            folded: None,
+        }
+    }
+
+    fn node(&self, state: &SmallPokerState) -> Node {
+        if state.hole.len() < 2 {
+            Node::Chance
+        } else if state.folded.is_some() {
+            Node::Terminal
+        } else if Self::round_over(state) {
+            if state.rounds.len() < self.bet_sizes.len() {
+                Node::Chance
+            } else {
+                Node::Terminal
+            }
+        } else {
+            Node::Player(state.rounds.last().unwrap().len() % 2)
+        }
+    }
+
+    fn outcomes(&self, state: &SmallPokerState) -> Vec<(SmallPokerState, f64)> {
+        let dead: Vec<Card> = state.hole.iter().chain(&state.board).copied().collect();
+        let cards = self.deck.remaining(&dead);
+        let p = 1.0 / cards.len() as f64;
+        cards
+            .into_iter()
+            .map(|card| {
+                let mut next = state.clone();
+                if next.hole.len() < 2 {
+                    next.hole.push(card);
+                } else {
+                    next.board.push(card);
+                    next.rounds.push(vec![]);
+                }
+                (next, p)
+            })
+            .collect()
+    }
+
+    fn actions(&self, state: &SmallPokerState) -> Vec<Move> {
+        let round = state.rounds.last().unwrap();
+        let bets = round.iter().filter(|m| matches!(m, Move::Bet | Move::Raise)).count();
+        match (round.last(), bets < self.max_bets) {
+            (Some(Move::Bet | Move::Raise), true) => vec![Move::Fold, Move::Call, Move::Raise],
+            (Some(Move::Bet | Move::Raise), false) => vec![Move::Fold, Move::Call],
+            _ => vec![Move::Check, Move::Bet],
+        }
+    }
+
+    fn play(&self, state: &SmallPokerState, action: Move) -> SmallPokerState {
+        let mut next = state.clone();
+        let player = state.rounds.last().unwrap().len() % 2;
+        let top = next.contributed[0].max(next.contributed[1]);
+        match action {
+            Move::Fold => next.folded = Some(player),
+            Move::Check => {}
+            Move::Call => next.contributed[player] = top,
+            Move::Bet | Move::Raise => next.contributed[player] = top + self.bet_sizes[state.rounds.len() - 1],
+        }
+        next.rounds.last_mut().unwrap().push(action);
+        next
+    }
+
+    fn payoff(&self, state: &SmallPokerState, player: usize) -> f64 {
+        let opponent = 1 - player;
+        let winner = match state.folded {
+            Some(folded) => Some(1 - folded),
+            None => match self.strength(state, player).cmp(&self.strength(state, opponent)) {
+                std::cmp::Ordering::Greater => Some(player),
+                std::cmp::Ordering::Less => Some(opponent),
+                std::cmp::Ordering::Equal => None,
+            },
+        };
+        match winner {
+            Some(w) if w == player => state.contributed[opponent],
+            Some(_) => -state.contributed[player],
+            None => 0.0,
+        }
+    }
+
+    /// The value of the private card, the values of the board and the moves of each round, e.g. "K:Q:bc/k".
+    ///
+    fn info_set(&self, state: &SmallPokerState) -> String {
+        let value = |card: &Card| match card.val {
+            13 => 'K',
+            12 => 'Q',
+            _ => 'J',
+        };
+        let player = state.rounds.last().unwrap().len() % 2;
+        let board: String = state.board.iter().map(value).collect();
+        let rounds: Vec<String> = state
+            .rounds
+            .iter()
+            .map(|round| {
+                round
+                    .iter()
+                    .map(|m| match m {
+                        Move::Fold => 'f',
+                        Move::Check => 'k',
+                        Move::Call => 'c',
+                        Move::Bet => 'b',
+                        Move::Raise => 'r',
+                    })
+                    .collect()
+            })
+            .collect();
+        format!("{}:{}:{}", value(&state.hole[player]), board, rounds.join("/"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Cfr, GameTree, Move, Node, SmallPoker};
+
+    #[test]
+    fn cfr_kuhn_tree() {
+        let kuhn = SmallPoker::kuhn();
+        let root = kuhn.root();
+        assert_eq!(kuhn.node(&root), Node::Chance);
+        let deal = &kuhn.outcomes(&kuhn.outcomes(&root)[2].0)[0].0;
+        assert_eq!(kuhn.node(deal), Node::Player(0));
+        assert_eq!(kuhn.info_set(deal), "K::");
+        assert_eq!(kuhn.actions(deal), vec![Move::Check, Move::Bet]);
+
+        // the King bets, the Jack calls and loses two chips
+        let bet = kuhn.play(deal, Move::Bet);
+        assert_eq!(kuhn.info_set(&bet), "J::b");
+        assert_eq!(kuhn.actions(&bet), vec![Move::Fold, Move::Call]);
+        let called = kuhn.play(&bet, Move::Call);
+        assert_eq!(kuhn.node(&called), Node::Terminal);
+        assert_eq!(kuhn.payoff(&called, 0), 2.0);
+        assert_eq!(kuhn.payoff(&kuhn.play(&bet, Move::Fold), 1), -1.0);
+    }
+
+    #[test]
+    fn cfr_kuhn() {
+        let mut vanilla = Cfr::new(SmallPoker::kuhn());
+        vanilla.train(1_000);
+        let mut plus = Cfr::plus(SmallPoker::kuhn());
+        plus.train(1_000);
+        let game = SmallPoker::kuhn();
+        assert!(vanilla.strategy().exploitability(&game) < 0.01);
+        assert!(plus.strategy().exploitability(&game) < vanilla.strategy().exploitability(&game));
+
+        // the value of the game is -1/18 for the first player
+        let strategy = plus.strategy();
+        assert!((strategy.value(&game) + 1.0 / 18.0).abs() < 1e-3, "{}", strategy.value(&game));
+        // the second player calls a bet with a King, folds the Jack, and bluffs with a Jack a third of the times after a check
+        assert!(strategy.probability("K::b", Move::Call).unwrap() > 0.99);
+        assert!(strategy.probability("J::b", Move::Fold).unwrap() > 0.99);
+        assert!((strategy.probability("J::k", Move::Bet).unwrap() - 1.0 / 3.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn cfr_leduc() {
+        let game = SmallPoker::leduc();
+        let mut solver = Cfr::plus(SmallPoker::leduc());
+        solver.train(1);
+        let start = solver.strategy().exploitability(&game);
+        solver.train(49);
+        let strategy = solver.strategy();
+        let exploitability = strategy.exploitability(&game);
+        assert!(exploitability < start / 4.0, "{start} {exploitability}");
+        // 288 information sets
+        assert_eq!(strategy.info_sets.len(), 288);
+        // the value of the game is about -0.086 for the first player
+        assert!((strategy.value(&game) + 0.086).abs() < 0.05, "{}", strategy.value(&game));
+    }
+}
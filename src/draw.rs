@@ -0,0 +1,218 @@
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use thiserror::Error;
+
+use crate::card::{Card, Deck, Hand};
+use crate::match_evaluator::{MatchHandEvaluator, Score};
+use crate::showdown::Showdown;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DrawError {
+    #[error("A Five-Card Draw game needs from 2 to 7 players")]
+    InvalidPlayers,
+
+    #[error("The player can't discard more than {0} cards")]
+    TooManyDiscards(usize),
+
+    #[error("The discarded cards must be distinct cards of the player's hand")]
+    NotInHand,
+
+    #[error("The player has already drawn, or folded")]
+    CantDraw,
+
+    #[error("All the players still in the hand must draw before the showdown")]
+    NotDrawn,
+
+    #[error("The deck has run out of cards")]
+    EmptyDeck,
+}
+
+/// The [`DrawTable`] struct. It deals a Five-Card Draw hand to players sitting in seat order, lets each of them discard and draw once, and settles the showdown.
+///
+/// When the deck runs out of cards, the muck (the cards discarded or folded by the other players) is shuffled to form a new deck.
+/// The discards of the player drawing are never reshuffled into the cards they draw.
+///
+#[derive(Debug, Clone)]
+pub struct DrawTable {
+    pub hands: Vec<Hand>,
+    pub folded: Vec<bool>,
+    pub drawn: Vec<bool>,
+    pub max_discards: usize,
+    deck: Deck,
+    muck: Vec<Card>,
+}
+
+impl DrawTable {
+    /// It sits the given number of players at the table and deals them 5 cards each from the `deck`, one at a time and in seat order.
+    ///
+    /// Each player will be allowed to discard up to `max_discards` cards (i.e. 3, or 5 to allow any draw).
+    ///
+    pub fn new(players: usize, max_discards: usize, mut deck: Deck) -> Result<DrawTable, DrawError> {
+        if !(2..=7).contains(&players) {
+            return Err(DrawError::InvalidPlayers);
+        }
+        let mut cards: Vec<Vec<Card>> = vec![vec![]; players];
+        for _ in 0..5 {
+            for hand in cards.iter_mut() {
+                hand.push(deck.deal().ok_or(DrawError::EmptyDeck)?);
+            }
+        }
+        Ok(DrawTable {
+            hands: cards.into_iter().map(|c| Hand::new(c.try_into().unwrap())).collect(),
+            folded: vec![false; players],
+            drawn: vec![false; players],
+            max_discards: max_discards.min(5),
+            deck,
+            muck: vec![],
+        })
+    }
+
+    /// The player at the given seat discards the given cards of the hand and draws as many replacements, returning the new [Hand].
+    ///
+    /// Standing pat is drawing with no discards. Every player can draw only once.
+    ///
+    pub fn draw(&mut self, player: usize, discards: &[Card]) -> Result<Hand, DrawError> {
+        if self.folded[player] || self.drawn[player] {
+            return Err(DrawError::CantDraw);
+        }
+        if discards.len() > self.max_discards {
+            return Err(DrawError::TooManyDiscards(self.max_discards));
+        }
+        let mut cards = *self.hands[player].get_hand_slice();
+        let mut positions = vec![];
+        for discard in discards {
+            match cards.iter().position(|c| c == discard) {
+                Some(i) if !positions.contains(&i) => positions.push(i),
+                _ => return Err(DrawError::NotInHand),
+            }
+        }
+        if self.deck.remaining(&[]).len() + self.muck.len() < discards.len() {
+            return Err(DrawError::EmptyDeck);
+        }
+
+        for i in positions {
+            cards[i] = match self.deck.deal() {
+                Some(card) => card,
+                None => {
+                    self.reshuffle();
+                    self.deck.deal().ok_or(DrawError::EmptyDeck)?
+                }
+            };
+        }
+        self.muck.extend_from_slice(discards);
+        self.hands[player] = Hand::new(cards);
+        self.drawn[player] = true;
+        Ok(self.hands[player].clone())
+    }
+
+    /// The player at the given seat folds, giving up the hand: the cards go to the muck.
+    ///
+    pub fn fold(&mut self, player: usize) {
+        if !self.folded[player] {
+            self.folded[player] = true;
+            self.muck.extend_from_slice(self.hands[player].get_hand_slice());
+        }
+    }
+
+    /// It awards the `pot` to the best hands among the players who haven't folded, comparing their [Score], kickers included.
+    ///
+    pub fn showdown(&self, pot: u64) -> Result<Showdown, DrawError> {
+        let live = || self.hands.iter().zip(&self.folded).zip(&self.drawn);
+        if live().any(|((_, folded), drawn)| !folded && !drawn) {
+            return Err(DrawError::NotDrawn);
+        }
+        let scores: Vec<Option<Score>> = live()
+            .map(|((hand, folded), _)| (!folded).then(|| MatchHandEvaluator::score(&mut hand.clone())))
+            .collect();
+        Ok(Showdown::high(pot, &scores))
+    }
+
+    /// function that shuffles the muck to form a new deck
+    ///
+    fn reshuffle(&mut self) {
+        let mut cards = std::mem::take(&mut self.muck);
+        cards.shuffle(&mut thread_rng());
+        self.deck = Deck::new(cards);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DrawError, DrawTable};
+    use crate::card::{Card, Deck, Hand};
+    use crate::{hand, newcard};
+
+    /// a deck dealing the given hands, one card at a time, followed by the given draws
+    fn deck(hands: &[[&str; 5]], draws: &[&str]) -> Deck {
+        let mut cards = vec![];
+        for i in 0..5 {
+            cards.extend(hands.iter().map(|h| newcard![h[i]]));
+        }
+        cards.extend(draws.iter().map(|c| newcard![*c]));
+        Deck::new(cards)
+    }
+
+    #[test]
+    fn draw_deal_and_draw() {
+        let hands = [["Ah", "Kd", "7c", "7s", "2h"], ["Qh", "Qd", "Qc", "9s", "3h"]];
+        let mut table = DrawTable::new(2, 3, deck(&hands, &["7h", "4c", "3c", "5s"])).unwrap();
+        assert_eq!(table.hands[0], hand!["Ah", "Kd", "7c", "7s", "2h"]);
+
+        assert_eq!(table.showdown(100), Err(DrawError::NotDrawn));
+        assert_eq!(
+            table.draw(0, &[newcard!["Kd"], newcard!["2h"]]),
+            Ok(hand!["Ah", "7h", "7c", "7s", "4c"])
+        );
+        assert_eq!(table.draw(0, &[]), Err(DrawError::CantDraw));
+
+        let four = [newcard!["Qh"], newcard!["Qd"], newcard!["Qc"], newcard!["9s"]];
+        assert_eq!(table.draw(1, &four), Err(DrawError::TooManyDiscards(3)));
+        assert_eq!(table.draw(1, &[newcard!["Ah"]]), Err(DrawError::NotInHand));
+        assert_eq!(table.draw(1, &[newcard!["9s"], newcard!["9s"]]), Err(DrawError::NotInHand));
+        assert_eq!(table.draw(1, &[]), Ok(hand!["Qh", "Qd", "Qc", "9s", "3h"]));
+    }
+
+    #[test]
+    fn draw_reshuffle() {
+        let hands = [["Ah", "Kd", "7c", "7s", "2h"], ["Qh", "Qd", "Qc", "9s", "3h"]];
+        let mut table = DrawTable::new(2, 5, deck(&hands, &["7h", "4c"])).unwrap();
+        table.draw(0, &[newcard!["Kd"], newcard!["2h"]]).unwrap();
+
+        // the deck is over, the discards of the first player are reshuffled
+        let hand = table.draw(1, &[newcard!["9s"], newcard!["3h"]]).unwrap();
+        let cards = hand.get_hand_slice();
+        assert!(cards.contains(&newcard!["Kd"]));
+        assert!(cards.contains(&newcard!["2h"]));
+
+        // no cards left at all
+        let mut table = DrawTable::new(2, 5, deck(&hands, &["7h"])).unwrap();
+        table.draw(0, &[newcard!["2h"]]).unwrap();
+        assert_eq!(table.draw(1, &[newcard!["9s"], newcard!["3h"]]), Err(DrawError::EmptyDeck));
+        assert_eq!(table.draw(1, &[newcard!["9s"]]).unwrap().get_hand_slice()[3], newcard!["2h"]);
+    }
+
+    #[test]
+    fn draw_showdown() {
+        // same two pair, the kicker decides
+        let hands = [["Kh", "Kd", "5c", "5s", "9h"], ["Ks", "Kc", "5h", "5d", "8c"]];
+        let mut table = DrawTable::new(2, 3, deck(&hands, &[])).unwrap();
+        table.draw(0, &[]).unwrap();
+        table.draw(1, &[]).unwrap();
+        assert_eq!(table.showdown(100).unwrap().payouts, vec![100, 0]);
+
+        let hands = [
+            ["Ah", "Kh", "Qh", "Jh", "9c"],
+            ["As", "Ks", "Qs", "Js", "9d"],
+            ["2c", "2d", "3c", "4s", "5h"],
+        ];
+        let mut table = DrawTable::new(3, 3, deck(&hands, &[])).unwrap();
+        table.draw(0, &[]).unwrap();
+        table.draw(1, &[]).unwrap();
+        table.fold(2);
+        let showdown = table.showdown(101).unwrap();
+        assert_eq!(showdown.high, vec![0, 1]);
+        assert_eq!(showdown.payouts, vec![51, 50, 0]);
+
+        assert_eq!(DrawTable::new(8, 3, Deck::create_shuffled_deck()).err(), Some(DrawError::InvalidPlayers));
+    }
+}
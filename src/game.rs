@@ -0,0 +1,724 @@
+use thiserror::Error;
+
+use crate::card::{Card, Deck};
+use crate::match_evaluator::{MatchHandEvaluator, Score};
+use crate::omaha::OmahaEvaluator;
+use crate::pot::Ledger;
+
+/// The [`Street`] enum. It represents the betting rounds of a Hold'em hand.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Street {
+    /// Two hole cards each, no board.
+    Preflop,
+    /// Three board cards.
+    Flop,
+    /// The fourth board card.
+    Turn,
+    /// The fifth board card.
+    River,
+}
+
+impl Street {
+    /// It returns the [Street] played after this one, if any.
+    ///
+    pub const fn next(&self) -> Option<Street> {
+        match self {
+            Street::Preflop => Some(Street::Flop),
+            Street::Flop => Some(Street::Turn),
+            Street::Turn => Some(Street::River),
+            Street::River => None,
+        }
+    }
+
+    /// It returns the number of board cards dealt up to this [Street].
+    ///
+    pub const fn board_size(&self) -> usize {
+        match self {
+            Street::Preflop => 0,
+            Street::Flop => 3,
+            Street::Turn => 4,
+            Street::River => 5,
+        }
+    }
+}
+
+/// The [`Action`] enum. The moves of the player to act.
+///
+/// The amounts of [`Action::Bet`] and [`Action::Raise`] are the total the player puts in on the current street, i.e. "raise to".
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    Bet(u64),
+    Raise(u64),
+    /// Calling or raising with the whole stack, whatever the minimum raise is.
+    AllIn,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GameError {
+    #[error("A Hold'em game needs from 2 to 10 players")]
+    InvalidPlayers,
+
+    #[error("Every player needs chips to be dealt in")]
+    EmptyStack,
+
+    #[error("The big blind must be positive and at least the small blind")]
+    InvalidBlinds,
+
+    #[error("The hand is over")]
+    HandOver,
+
+    #[error("The player can't check facing a bet")]
+    CantCheck,
+
+    #[error("There is no bet to call")]
+    NothingToCall,
+
+    #[error("A bet opens the betting, facing a bet the player can only raise")]
+    CantBet,
+
+    #[error("There is no bet to raise")]
+    NothingToRaise,
+
+    #[error("The player is not allowed to raise")]
+    RaiseNotAllowed,
+
+    #[error("The amount must be from {0} to {1}")]
+    InvalidAmount(u64, u64),
+
+    #[error("The deck has run out of cards")]
+    EmptyDeck,
+}
+
+/// The [`Blinds`] struct. The forced bets of a hand: the small and the big blind, plus an ante paid by every player.
+///
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Blinds {
+    pub small: u64,
+    pub big: u64,
+    pub ante: u64,
+}
+
+/// The [`Betting`] enum. The betting structure of the game, setting how much a player can bet or raise.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Betting {
+    /// A bet or a raise can be as large as the whole stack.
+    NoLimit,
+    /// A bet or a raise can be at most the size of the pot after calling.
+    PotLimit,
+    /// The bets and raises are of a fixed size: the small bet preflop and on the flop, the big bet on the turn and on the river,
+    /// and at most `cap` bets and raises are allowed on a street, the big blind counting as the first bet preflop.
+    FixedLimit { small_bet: u64, big_bet: u64, cap: usize },
+}
+
+impl Betting {
+    /// It returns the minimum bet on the given [Street], as well as the size of a raise in fixed limit.
+    ///
+    pub const fn min_bet(&self, street: Street, big_blind: u64) -> u64 {
+        match (self, street) {
+            (Betting::FixedLimit { small_bet, .. }, Street::Preflop | Street::Flop) => *small_bet,
+            (Betting::FixedLimit { big_bet, .. }, Street::Turn | Street::River) => *big_bet,
+            _ => big_blind,
+        }
+    }
+}
+
+/// The [`Seat`] struct. A player at the table: the chips behind, the hole cards and the chips put in the pot.
+///
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Seat {
+    pub stack: u64,
+    pub hole: Vec<Card>,
+    /// The chips put in on the current street.
+    pub bet: u64,
+    /// The chips put in during the whole hand, antes included.
+    pub contributed: u64,
+    pub folded: bool,
+}
+
+impl Seat {
+    /// It tells if the player is all-in, i.e. still in the hand without any chip behind.
+    ///
+    pub fn is_all_in(&self) -> bool {
+        !self.folded && self.stack == 0
+    }
+}
+
+/// The [`LegalActions`] struct. What the player to act is allowed to do, besides folding.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct LegalActions {
+    /// Whether the player can check.
+    pub check: bool,
+    /// The chips needed to call, capped by the stack (0 when there's nothing to call).
+    pub call: u64,
+    /// The minimum and maximum total amount to bet or raise to on the street, if the player can bet or raise.
+    pub raise: Option<(u64, u64)>,
+}
+
+/// The [`Game`] struct. A Hold'em or Omaha hand played with a [`Betting`] structure, from the blinds to the showdown.
+///
+/// The players sit in seat order, the button rotating clockwise: the small blind is the first seat left of the button (the button itself heads-up)
+/// and the big blind the next one. The hand advances by itself: after each [`Action`], the next player to act is set, the board is dealt
+/// when a betting round is over, and once the hand is over the pot is awarded and the stacks updated.
+///
+/// The minimum bet is the big blind (or the bet size of the street in fixed limit), and a raise must be at least as large as the last bet or raise
+/// of the street. A player can always go all-in for less than the minimum, but an all-in raise smaller than the minimum doesn't reopen the betting
+/// to the players who have already acted.
+///
+#[derive(Debug, Clone)]
+pub struct Game {
+    pub seats: Vec<Seat>,
+    pub button: usize,
+    pub blinds: Blinds,
+    pub betting: Betting,
+    pub board: Vec<Card>,
+    pub street: Street,
+    /// The seat of the player to act, `None` once the hand is over.
+    pub to_act: Option<usize>,
+    /// The chips won by each player, empty until the hand is over.
+    pub payouts: Vec<u64>,
+    current_bet: u64,
+    last_raise: u64,
+    /// The number of bets and raises made on the current street.
+    raises: usize,
+    omaha: bool,
+    acted: Vec<bool>,
+    can_raise: Vec<bool>,
+    deck: Deck,
+}
+
+impl Game {
+    /// It starts a Hold'em hand with players sitting with the given stacks: antes and blinds are posted and two hole cards are dealt to each player
+    /// from the `deck`, one at a time, starting left of the button.
+    ///
+    pub fn new(stacks: &[u64], button: usize, blinds: Blinds, betting: Betting, deck: Deck) -> Result<Game, GameError> {
+        Self::deal(stacks, button, blinds, betting, deck, false)
+    }
+
+    /// It starts an Omaha hand as [Game::new] does, dealing four hole cards to each player.
+    ///
+    /// At showdown, each player makes the best hand with exactly two hole cards and three board cards.
+    ///
+    pub fn omaha(stacks: &[u64], button: usize, blinds: Blinds, betting: Betting, deck: Deck) -> Result<Game, GameError> {
+        Self::deal(stacks, button, blinds, betting, deck, true)
+    }
+
+    /// function that starts a hand, dealing two hole cards to each player, or four in Omaha
+    ///
+    fn deal(stacks: &[u64], button: usize, blinds: Blinds, betting: Betting, deck: Deck, omaha: bool) -> Result<Game, GameError> {
+        let n = stacks.len();
+        if !(2..=10).contains(&n) || button >= n {
+            return Err(GameError::InvalidPlayers);
+        }
+        if stacks.contains(&0) {
+            return Err(GameError::EmptyStack);
+        }
+        if blinds.big == 0 || blinds.small > blinds.big {
+            return Err(GameError::InvalidBlinds);
+        }
+        let mut game = Game {
+            seats: stacks
+                .iter()
+                .map(|stack| Seat {
+                    stack: *stack,
+                    ..Seat::default()
+                })
+                .collect(),
+            button,
+            blinds,
+            betting,
+            board: vec![],
+            street: Street::Preflop,
+            to_act: None,
+            payouts: vec![],
+            current_bet: blinds.big,
+            last_raise: betting.min_bet(Street::Preflop, blinds.big),
+            // the big blind opens the betting
+            raises: 1,
+            omaha,
+            acted: vec![false; n],
+            can_raise: vec![true; n],
+            deck,
+        };
+
+        for i in 0..n {
+            let ante = blinds.ante.min(game.seats[i].stack);
+            game.seats[i].stack -= ante;
+            game.seats[i].contributed += ante;
+        }
+        let small = if n == 2 { button } else { (button + 1) % n };
+        game.put(small, blinds.small);
+        game.put((small + 1) % n, blinds.big);
+
+        for _ in 0..if omaha { 4 } else { 2 } {
+            for i in 0..n {
+                let card = game.deck.deal().ok_or(GameError::EmptyDeck)?;
+                game.seats[(button + 1 + i) % n].hole.push(card);
+            }
+        }
+        game.next(small + 2)?;
+        Ok(game)
+    }
+
+    /// It returns the total of the chips in the pot, the bets of the current street included.
+    ///
+    pub fn pot(&self) -> u64 {
+        self.seats.iter().map(|s| s.contributed).sum()
+    }
+
+    /// It tells if the hand is over.
+    ///
+    pub fn is_over(&self) -> bool {
+        self.to_act.is_none()
+    }
+
+    /// It returns the [LegalActions] of the player to act, if the hand isn't over.
+    ///
+    pub fn legal_actions(&self) -> Option<LegalActions> {
+        let i = self.to_act?;
+        let call = self.to_call(i);
+        Some(LegalActions {
+            check: call == 0,
+            call,
+            raise: self.raise_range(i),
+        })
+    }
+
+    /// The player to act makes the given [Action], then the hand advances to the next player, street or showdown.
+    ///
+    pub fn act(&mut self, action: Action) -> Result<(), GameError> {
+        let i = self.to_act.ok_or(GameError::HandOver)?;
+        let call = self.to_call(i);
+        match action {
+            Action::Fold => self.seats[i].folded = true,
+            Action::Check if call > 0 => return Err(GameError::CantCheck),
+            Action::Check => {}
+            Action::Call if call == 0 => return Err(GameError::NothingToCall),
+            Action::Call => self.put(i, call),
+            Action::Bet(_) if self.current_bet > 0 => return Err(GameError::CantBet),
+            Action::Raise(_) if self.current_bet == 0 => return Err(GameError::NothingToRaise),
+            Action::Bet(to) | Action::Raise(to) => {
+                let (min, max) = self.raise_range(i).ok_or(GameError::RaiseNotAllowed)?;
+                if !(min..=max).contains(&to) {
+                    return Err(GameError::InvalidAmount(min, max));
+                }
+                self.raise(i, to);
+            }
+            Action::AllIn => {
+                let seat = &self.seats[i];
+                let to = seat.bet + seat.stack;
+                if to <= self.current_bet {
+                    self.put(i, seat.stack);
+                } else {
+                    // in pot and fixed limit the stack may be larger than the maximum raise
+                    let (min, max) = self.raise_range(i).ok_or(GameError::RaiseNotAllowed)?;
+                    if to > max {
+                        return Err(GameError::InvalidAmount(min, max));
+                    }
+                    self.raise(i, to);
+                }
+            }
+        }
+        self.acted[i] = true;
+        self.next(i + 1)
+    }
+
+    /// function that returns the chips the player needs to call, capped by the stack
+    ///
+    fn to_call(&self, i: usize) -> u64 {
+        let seat = &self.seats[i];
+        (self.current_bet.saturating_sub(seat.bet)).min(seat.stack)
+    }
+
+    /// function that returns the range of the amounts the player can bet or raise to according to the betting structure, if allowed
+    ///
+    fn raise_range(&self, i: usize) -> Option<(u64, u64)> {
+        let seat = &self.seats[i];
+        let all_in = seat.bet + seat.stack;
+        // nobody left to raise against
+        let others = (0..self.seats.len()).any(|j| j != i && !self.seats[j].folded && !self.seats[j].is_all_in());
+        if !self.can_raise[i] || !others || all_in <= self.current_bet {
+            return None;
+        }
+        let min_bet = self.betting.min_bet(self.street, self.blinds.big);
+        let min = if self.current_bet == 0 {
+            min_bet
+        } else {
+            self.current_bet + self.last_raise
+        };
+        let max = match self.betting {
+            Betting::NoLimit => all_in,
+            // the pot after calling, raised by its size
+            Betting::PotLimit => {
+                let call = self.current_bet - seat.bet;
+                (self.current_bet + self.pot() + call).max(min)
+            }
+            Betting::FixedLimit { cap, .. } => {
+                if self.raises >= cap {
+                    return None;
+                }
+                self.current_bet + min_bet
+            }
+        };
+        let min = match self.betting {
+            Betting::FixedLimit { .. } => max,
+            _ => min,
+        };
+        Some((min.min(all_in), max.min(all_in)))
+    }
+
+    /// function that puts chips of the player in the pot, up to the whole stack
+    ///
+    fn put(&mut self, i: usize, amount: u64) {
+        let seat = &mut self.seats[i];
+        let amount = amount.min(seat.stack);
+        seat.stack -= amount;
+        seat.bet += amount;
+        seat.contributed += amount;
+    }
+
+    /// function that bets or raises to the given amount, reopening the betting if the raise is a full one
+    ///
+    fn raise(&mut self, i: usize, to: u64) {
+        let size = to - self.current_bet;
+        self.put(i, to - self.seats[i].bet);
+        let full = size >= self.last_raise || self.current_bet == 0;
+        for j in 0..self.seats.len() {
+            if j != i {
+                // an incomplete raise only lets the players who have already acted call or fold
+                self.can_raise[j] = full || (self.can_raise[j] && !self.acted[j]);
+                self.acted[j] = false;
+            }
+        }
+        if full {
+            self.last_raise = size.max(self.betting.min_bet(self.street, self.blinds.big));
+            self.raises += 1;
+        }
+        self.current_bet = to;
+    }
+
+    /// function that finds the next player to act from the given seat on, dealing the next streets when the betting round is over
+    ///
+    fn next(&mut self, from: usize) -> Result<(), GameError> {
+        let n = self.seats.len();
+        if self.seats.iter().filter(|s| !s.folded).count() == 1 {
+            return self.finish();
+        }
+        let pending = (0..n).map(|k| (from + k) % n).find(|j| {
+            let seat = &self.seats[*j];
+            !seat.folded && !seat.is_all_in() && (!self.acted[*j] || seat.bet < self.current_bet)
+        });
+        // a single player with chips behind, not facing a bet, has nobody to bet against
+        let active = self.seats.iter().filter(|s| !s.folded && !s.is_all_in()).count();
+        match pending {
+            Some(j) if active > 1 || self.seats[j].bet < self.current_bet => {
+                self.to_act = Some(j);
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        // the betting round is over
+        let Some(street) = self.street.next() else {
+            return self.finish();
+        };
+        for seat in self.seats.iter_mut() {
+            seat.bet = 0;
+        }
+        self.acted = vec![false; n];
+        self.can_raise = vec![true; n];
+        self.current_bet = 0;
+        self.last_raise = self.betting.min_bet(street, self.blinds.big);
+        self.raises = 0;
+        while self.board.len() < street.board_size() {
+            self.board.push(self.deck.deal().ok_or(GameError::EmptyDeck)?);
+        }
+        self.street = street;
+        self.next(self.button + 1)
+    }
+
+    /// function that ends the hand, awarding the pot and updating the stacks
+    ///
+    fn finish(&mut self) -> Result<(), GameError> {
+        self.to_act = None;
+        let live = self.seats.iter().filter(|s| !s.folded).count();
+        // the hands are compared only at showdown, otherwise the last player left wins without showing
+        let scores: Vec<Option<Score>> = self
+            .seats
+            .iter()
+            .map(|seat| {
+                (!seat.folded && live > 1).then(|| match <&[Card; 5]>::try_from(&self.board[..]) {
+                    Ok(board) if self.omaha => OmahaEvaluator::score(&seat.hole, board),
+                    _ => {
+                        let cards: Vec<Card> = seat.hole.iter().chain(&self.board).copied().collect();
+                        MatchHandEvaluator::best_score(&cards)
+                    }
+                })
+            })
+            .collect();
+        let contributions: Vec<u64> = self.seats.iter().map(|s| s.contributed).collect();
+        let folded: Vec<bool> = self.seats.iter().map(|s| s.folded).collect();
+        self.payouts = Ledger::high(&contributions, &folded, self.button, &scores).payouts;
+
+        for (seat, payout) in self.seats.iter_mut().zip(&self.payouts) {
+            seat.stack += payout;
+            seat.bet = 0;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Action, Betting, Blinds, Game, GameError, LegalActions, Street};
+    use crate::card::{Card, Deck};
+    use crate::newcard;
+
+    const BLINDS: Blinds = Blinds {
+        small: 5,
+        big: 10,
+        ante: 0,
+    };
+
+    /// a deck dealing the given hole cards, one at a time starting left of the button, followed by the board
+    fn deck(holes: &[[&str; 2]], board: [&str; 5]) -> Deck {
+        let mut cards: Vec<Card> = holes.iter().map(|h| newcard![h[0]]).collect();
+        cards.extend(holes.iter().map(|h| newcard![h[1]]));
+        cards.extend(board.iter().map(|c| newcard![*c]));
+        Deck::new(cards)
+    }
+
+    #[test]
+    fn game_blinds_and_deal() {
+        let blinds = Blinds { ante: 1, ..BLINDS };
+        let game = Game::new(&[1000, 1000, 1000, 1000], 0, blinds, Betting::NoLimit, Deck::create_shuffled_deck()).unwrap();
+        assert_eq!(game.seats[1].bet, 5);
+        assert_eq!(game.seats[2].bet, 10);
+        assert_eq!(game.seats[2].stack, 989);
+        assert_eq!(game.pot(), 19);
+        assert!(game.seats.iter().all(|s| s.hole.len() == 2));
+        // under the gun acts first
+        assert_eq!(game.to_act, Some(3));
+        assert_eq!(
+            game.legal_actions(),
+            Some(LegalActions {
+                check: false,
+                call: 10,
+                raise: Some((20, 999)),
+            })
+        );
+
+        // heads-up, the button posts the small blind and acts first preflop
+        let game = Game::new(&[1000, 1000], 1, BLINDS, Betting::NoLimit, Deck::create_shuffled_deck()).unwrap();
+        assert_eq!(game.seats[1].bet, 5);
+        assert_eq!(game.to_act, Some(1));
+
+        assert_eq!(Game::new(&[1000], 0, BLINDS, Betting::NoLimit, Deck::create_shuffled_deck()).err(), Some(GameError::InvalidPlayers));
+        assert_eq!(Game::new(&[1000, 0], 0, BLINDS, Betting::NoLimit, Deck::create_shuffled_deck()).err(), Some(GameError::EmptyStack));
+        // a zero big blind would reopen the action with every bet of nothing
+        let blinds = Blinds { big: 0, ..BLINDS };
+        assert_eq!(Game::new(&[1000, 1000], 0, blinds, Betting::NoLimit, Deck::create_shuffled_deck()).err(), Some(GameError::InvalidBlinds));
+        let blinds = Blinds { small: 20, ..BLINDS };
+        assert_eq!(Game::new(&[1000, 1000], 0, blinds, Betting::NoLimit, Deck::create_shuffled_deck()).err(), Some(GameError::InvalidBlinds));
+    }
+
+    #[test]
+    fn game_min_raise() {
+        let mut game = Game::new(&[1000, 1000, 1000], 0, BLINDS, Betting::NoLimit, Deck::create_shuffled_deck()).unwrap();
+        assert_eq!(game.act(Action::Check), Err(GameError::CantCheck));
+        assert_eq!(game.act(Action::Bet(30)), Err(GameError::CantBet));
+        assert_eq!(game.act(Action::Raise(15)), Err(GameError::InvalidAmount(20, 1000)));
+        game.act(Action::Raise(30)).unwrap();
+        // the small blind re-raises by 50, so the next raise must be to 130 at least
+        game.act(Action::Raise(80)).unwrap();
+        assert_eq!(game.legal_actions().unwrap().raise, Some((130, 1000)));
+        game.act(Action::Fold).unwrap();
+        game.act(Action::Call).unwrap();
+        assert_eq!(game.pot(), 170);
+
+        // the flop: the small blind acts first, the minimum bet is the big blind
+        assert_eq!(game.street, Street::Flop);
+        assert_eq!(game.board.len(), 3);
+        assert_eq!(game.to_act, Some(1));
+        assert_eq!(game.act(Action::Raise(50)), Err(GameError::NothingToRaise));
+        assert_eq!(game.legal_actions().unwrap().raise, Some((10, 920)));
+        game.act(Action::Check).unwrap();
+        game.act(Action::Bet(100)).unwrap();
+        assert_eq!(game.to_act, Some(1));
+        game.act(Action::Fold).unwrap();
+
+        // the uncalled bet goes back
+        assert!(game.is_over());
+        assert_eq!(game.payouts, vec![270, 0, 0]);
+        assert_eq!(game.seats.iter().map(|s| s.stack).collect::<Vec<_>>(), vec![1090, 920, 990]);
+        assert_eq!(game.act(Action::Check), Err(GameError::HandOver));
+    }
+
+    #[test]
+    fn game_showdown() {
+        // the button, small and big blind
+        let holes = [["Ah", "Ad"], ["Kh", "Kd"], ["7c", "2s"]];
+        let board = ["Ac", "Kc", "9s", "4h", "3d"];
+        let mut game = Game::new(&[1000, 1000, 1000], 0, BLINDS, Betting::NoLimit, deck(&[holes[1], holes[2], holes[0]], board)).unwrap();
+        assert_eq!(game.seats[0].hole, vec![newcard!["Ah"], newcard!["Ad"]]);
+        game.act(Action::Call).unwrap();
+        game.act(Action::Call).unwrap();
+        // the big blind option
+        assert!(game.legal_actions().unwrap().check);
+        game.act(Action::Check).unwrap();
+        while !game.is_over() {
+            game.act(Action::Check).unwrap();
+        }
+        assert_eq!(game.board.len(), 5);
+        assert_eq!(game.payouts, vec![30, 0, 0]);
+    }
+
+    #[test]
+    fn game_all_in() {
+        // the big blind is all-in with the best hand and wins the main pot only
+        let holes = [["Kh", "Kd"], ["Ah", "Ad"], ["Qh", "Qd"]];
+        let board = ["2c", "5c", "9s", "Jh", "3d"];
+        let mut game = Game::new(&[1000, 1000, 100], 0, BLINDS, Betting::NoLimit, deck(&[holes[0], holes[1], holes[2]], board)).unwrap();
+        game.act(Action::Raise(300)).unwrap();
+        game.act(Action::Call).unwrap();
+        game.act(Action::AllIn).unwrap();
+        assert_eq!(game.street, Street::Flop);
+        game.act(Action::Bet(200)).unwrap();
+        game.act(Action::Call).unwrap();
+        while !game.is_over() {
+            game.act(Action::Check).unwrap();
+        }
+        // the main pot of 300, then the side pot of 2 x 200 + 2 x 200 between the others
+        assert_eq!(game.payouts, vec![0, 800, 300]);
+
+        // everybody all-in preflop, the board is run out
+        let mut game = Game::new(&[1000, 500, 100], 0, BLINDS, Betting::NoLimit, deck(&[holes[0], holes[1], holes[2]], board)).unwrap();
+        game.act(Action::AllIn).unwrap();
+        game.act(Action::AllIn).unwrap();
+        game.act(Action::Call).unwrap();
+        assert!(game.is_over());
+        assert_eq!(game.board.len(), 5);
+        assert_eq!(game.payouts, vec![500, 800, 300]);
+    }
+
+    #[test]
+    fn game_incomplete_raise() {
+        let mut game = Game::new(&[1000, 1000, 1000, 150], 0, BLINDS, Betting::NoLimit, Deck::create_shuffled_deck()).unwrap();
+        for _ in 0..4 {
+            if game.act(Action::Call).is_err() {
+                game.act(Action::Check).unwrap();
+            }
+        }
+        // the flop, from the small blind: bet 100, call, all-in for 140 (an incomplete raise)
+        assert_eq!(game.street, Street::Flop);
+        game.act(Action::Check).unwrap();
+        game.act(Action::Bet(100)).unwrap();
+        game.act(Action::AllIn).unwrap();
+        assert_eq!(game.seats[3].bet, 140);
+        // the button hasn't acted yet, so can still raise
+        assert_eq!(game.to_act, Some(0));
+        assert_eq!(game.legal_actions().unwrap().raise, Some((240, 990)));
+        game.act(Action::Call).unwrap();
+        // the small blind had only checked, it can raise too
+        assert!(game.legal_actions().unwrap().raise.is_some());
+        game.act(Action::Call).unwrap();
+        // the bettor can only call or fold
+        assert_eq!(game.to_act, Some(2));
+        assert_eq!(game.legal_actions().unwrap().raise, None);
+        assert_eq!(game.act(Action::Raise(300)), Err(GameError::RaiseNotAllowed));
+        game.act(Action::Call).unwrap();
+        assert_eq!(game.street, Street::Turn);
+    }
+
+    #[test]
+    fn game_short_all_in_bet() {
+        let mut game = Game::new(&[1000, 1000, 1000, 13], 0, BLINDS, Betting::NoLimit, Deck::create_shuffled_deck()).unwrap();
+        for _ in 0..4 {
+            if game.act(Action::Call).is_err() {
+                game.act(Action::Check).unwrap();
+            }
+        }
+        // the flop: check, check, all-in for 3, less than the big blind
+        assert_eq!(game.street, Street::Flop);
+        game.act(Action::Check).unwrap();
+        game.act(Action::Check).unwrap();
+        game.act(Action::AllIn).unwrap();
+        assert_eq!(game.seats[3].bet, 3);
+        // the minimum raise is still by the big blind
+        assert_eq!(game.to_act, Some(0));
+        assert_eq!(game.legal_actions().unwrap().raise, Some((13, 990)));
+        assert_eq!(game.act(Action::Raise(6)), Err(GameError::InvalidAmount(13, 990)));
+        game.act(Action::Raise(13)).unwrap();
+        assert_eq!(game.legal_actions().unwrap().raise, Some((23, 990)));
+    }
+
+    #[test]
+    fn game_pot_limit() {
+        let mut game = Game::new(&[1000, 1000, 1000], 0, BLINDS, Betting::PotLimit, Deck::create_shuffled_deck()).unwrap();
+        // calling 10 makes the pot 25, so the pot raise is to 35
+        assert_eq!(game.legal_actions().unwrap().raise, Some((20, 35)));
+        game.act(Action::Raise(35)).unwrap();
+        // the small blind calls 30 into a pot of 80, then raises 80 more
+        assert_eq!(game.legal_actions().unwrap().raise, Some((60, 115)));
+        assert_eq!(game.act(Action::AllIn), Err(GameError::InvalidAmount(60, 115)));
+        game.act(Action::Raise(115)).unwrap();
+        game.act(Action::Fold).unwrap();
+        game.act(Action::Call).unwrap();
+
+        // the flop: the pot is 240
+        assert_eq!(game.legal_actions().unwrap().raise, Some((10, 240)));
+    }
+
+    #[test]
+    fn game_fixed_limit() {
+        let betting = Betting::FixedLimit {
+            small_bet: 10,
+            big_bet: 20,
+            cap: 4,
+        };
+        let mut game = Game::new(&[1000, 1000, 1000], 0, BLINDS, betting, Deck::create_shuffled_deck()).unwrap();
+        assert_eq!(game.legal_actions().unwrap().raise, Some((20, 20)));
+        assert_eq!(game.act(Action::Raise(30)), Err(GameError::InvalidAmount(20, 20)));
+        game.act(Action::Raise(20)).unwrap();
+        game.act(Action::Raise(30)).unwrap();
+        assert_eq!(game.act(Action::AllIn), Err(GameError::InvalidAmount(40, 40)));
+        game.act(Action::Raise(40)).unwrap();
+        // the betting is capped
+        assert_eq!(game.legal_actions().unwrap().raise, None);
+        assert_eq!(game.act(Action::Raise(50)), Err(GameError::RaiseNotAllowed));
+        game.act(Action::Call).unwrap();
+        game.act(Action::Call).unwrap();
+        assert_eq!(game.pot(), 120);
+
+        // small bets on the flop, big bets on the turn
+        assert_eq!(game.street, Street::Flop);
+        assert_eq!(game.legal_actions().unwrap().raise, Some((10, 10)));
+        game.act(Action::Bet(10)).unwrap();
+        assert_eq!(game.legal_actions().unwrap().raise, Some((20, 20)));
+        game.act(Action::Call).unwrap();
+        game.act(Action::Call).unwrap();
+        assert_eq!(game.street, Street::Turn);
+        assert_eq!(game.legal_actions().unwrap().raise, Some((20, 20)));
+    }
+
+    #[test]
+    fn game_omaha() {
+        // with four clubs on the board, the ten of clubs would make a royal flush in Hold'em, but in Omaha the pair of fives wins
+        let holes = [["10c", "5h", "6h", "7h"], ["5s", "5d", "8h", "9h"]];
+        let mut cards: Vec<Card> = (0..4).flat_map(|k| holes.iter().map(move |h| newcard![h[k]])).collect();
+        cards.extend(["Ac", "Kc", "Qc", "Jc", "2d"].iter().map(|c| newcard![*c]));
+        let mut game = Game::omaha(&[1000, 1000], 1, BLINDS, Betting::PotLimit, Deck::new(cards)).unwrap();
+        assert!(game.seats.iter().all(|s| s.hole.len() == 4));
+        game.act(Action::Call).unwrap();
+        while !game.is_over() {
+            game.act(Action::Check).unwrap();
+        }
+        assert_eq!(game.payouts, vec![0, 20]);
+    }
+}
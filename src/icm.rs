@@ -0,0 +1,357 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use thiserror::Error;
+
+use crate::card::{Card, Deck};
+use crate::match_evaluator::MatchHandEvaluator;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum IcmError {
+    #[error("There are no chips in play")]
+    NoChips,
+
+    #[error("The exact model handles up to {0} players")]
+    TooManyPlayers(usize),
+
+    #[error("The same card is dealt twice")]
+    DuplicateCard,
+
+    #[error("At least one sample is needed")]
+    NoSamples,
+
+    #[error("The pusher and the caller must be two different players")]
+    InvalidPlayers,
+
+    #[error("Every player needs a stack covering the chips posted")]
+    InvalidPosted,
+}
+
+/// The [`Icm`] struct. The Independent Chip Model, converting tournament chip stacks into prize equity.
+///
+/// In the Malmuth-Harville model a player finishes first with a probability proportional to the stack,
+/// and each next position is assigned the same way among the players left.
+///
+pub struct Icm;
+
+impl Icm {
+    /// The largest field the exact model is computed for.
+    ///
+    pub const EXACT_PLAYERS: usize = 20;
+
+    /// It computes the exact prize equity of each stack, the `prizes` being the amounts paid to the finishing positions, the winner first.
+    ///
+    /// It enumerates every set of players who could fill the paid positions, so the cost grows with the number of players and of paid positions.
+    ///
+    pub fn equities(stacks: &[u64], prizes: &[f64]) -> Result<Vec<f64>, IcmError> {
+        let n = stacks.len();
+        if n > Self::EXACT_PLAYERS {
+            return Err(IcmError::TooManyPlayers(Self::EXACT_PLAYERS));
+        }
+        let total: u64 = stacks.iter().sum();
+        if total == 0 {
+            return Err(IcmError::NoChips);
+        }
+        let paid = prizes.len().min(n);
+
+        // the probability that the players of a set take the first positions, in any order
+        let mut probabilities = vec![0.0; 1 << n];
+        probabilities[0] = 1.0;
+        let mut equities = vec![0.0; n];
+        for mask in 0..1usize << n {
+            let p = probabilities[mask];
+            let places = mask.count_ones() as usize;
+            if p == 0.0 || places >= paid {
+                continue;
+            }
+            let left = total - (0..n).filter(|i| mask & (1 << i) != 0).map(|i| stacks[i]).sum::<u64>();
+            for i in (0..n).filter(|i| mask & (1 << i) == 0) {
+                let next = if left == 0 {
+                    // the busted players share the positions left
+                    1.0 / (n - places) as f64
+                } else {
+                    stacks[i] as f64 / left as f64
+                };
+                equities[i] += p * next * prizes[places];
+                probabilities[mask | (1 << i)] += p * next;
+            }
+        }
+        Ok(equities)
+    }
+
+    /// It estimates the prize equity of each stack by sampling `samples` finishing orders, for fields too large for [Icm::equities].
+    ///
+    pub fn sample_equities<R: Rng>(stacks: &[u64], prizes: &[f64], samples: usize, rng: &mut R) -> Result<Vec<f64>, IcmError> {
+        if stacks.iter().sum::<u64>() == 0 {
+            return Err(IcmError::NoChips);
+        }
+        if samples == 0 {
+            return Err(IcmError::NoSamples);
+        }
+        let n = stacks.len();
+        let mut equities = vec![0.0; n];
+        for _ in 0..samples {
+            // the finishing order of the Malmuth-Harville model is the one of exponential times with a rate equal to the stack
+            let mut times: Vec<(f64, usize)> = (0..n)
+                .map(|i| {
+                    let time = -(1.0 - rng.gen::<f64>()).ln() / stacks[i] as f64;
+                    (time, i)
+                })
+                .collect();
+            // the busted players all get an infinite time: shuffled before the stable sort, they share the positions left as in the exact model
+            times.shuffle(rng);
+            times.sort_by(|a, b| a.0.total_cmp(&b.0));
+            for ((_, i), prize) in times.iter().zip(prizes) {
+                equities[*i] += prize;
+            }
+        }
+        Ok(equities.iter().map(|e| e / samples as f64).collect())
+    }
+
+    /// It estimates the preflop all-in equity of the `hole` cards against the `range` of holdings of a single opponent,
+    /// dealing `samples` random holdings from the range (skipping the ones blocked by the hole cards) and boards: ties count half.
+    ///
+    pub fn preflop_equity<R: Rng>(
+        hole: &[Card; 2],
+        range: &[[Card; 2]],
+        samples: usize,
+        rng: &mut R,
+    ) -> Result<f64, IcmError> {
+        if hole[0] == hole[1] {
+            return Err(IcmError::DuplicateCard);
+        }
+        if samples == 0 {
+            return Err(IcmError::NoSamples);
+        }
+        let range: Vec<&[Card; 2]> = range.iter().filter(|h| !h.iter().any(|c| hole.contains(c))).collect();
+        if range.is_empty() {
+            return Ok(0.0);
+        }
+        let mut won = 0.0;
+        for _ in 0..samples {
+            let villain = range.choose(rng).unwrap();
+            won += Self::showdown(hole, villain, rng);
+        }
+        Ok(won / samples as f64)
+    }
+
+    /// It runs a random board for two holdings all-in preflop, returning the share of the pot won by the `hole` cards:
+    /// 1 for a win, 0.5 for a tie, 0 for a loss. The holdings must not share any card.
+    ///
+    pub fn showdown<R: Rng>(hole: &[Card; 2], villain: &[Card; 2], rng: &mut R) -> f64 {
+        let dead: Vec<Card> = hole.iter().chain(villain.iter()).copied().collect();
+        let mut cards = Deck::create_ordered_deck().remaining(&dead);
+        let (board, _) = cards.partial_shuffle(rng, 5);
+        let ours: Vec<Card> = hole.iter().chain(board.iter()).copied().collect();
+        let theirs: Vec<Card> = villain.iter().chain(board.iter()).copied().collect();
+        match MatchHandEvaluator::best_score(&ours).cmp(&MatchHandEvaluator::best_score(&theirs)) {
+            std::cmp::Ordering::Less => 1.0,
+            std::cmp::Ordering::Equal => 0.5,
+            std::cmp::Ordering::Greater => 0.0,
+        }
+    }
+}
+
+/// The [`PushFold`] struct. A preflop shove of the `pusher` into the `caller`, the last player left to act, e.g. the small blind into the big blind.
+///
+/// The `stacks` are the chips of the players before the hand, and `posted` the blinds and antes they have put in.
+/// If the pusher folds, or the caller calls and wins, the caller takes the pot.
+/// Called, each of the two players is all-in for the smaller stack, whatever was posted above it going back to its owner.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct PushFold {
+    pub stacks: Vec<u64>,
+    pub posted: Vec<u64>,
+    pub pusher: usize,
+    pub caller: usize,
+}
+
+impl PushFold {
+    /// It creates the spot, checking that the pusher and the caller are two of the players and that every stack covers the chips posted.
+    ///
+    pub fn new(stacks: Vec<u64>, posted: Vec<u64>, pusher: usize, caller: usize) -> Result<PushFold, IcmError> {
+        if pusher == caller || pusher >= stacks.len() || caller >= stacks.len() {
+            return Err(IcmError::InvalidPlayers);
+        }
+        if posted.len() != stacks.len() || stacks.iter().zip(&posted).any(|(s, p)| p > s) {
+            return Err(IcmError::InvalidPosted);
+        }
+        Ok(PushFold {
+            stacks,
+            posted,
+            pusher,
+            caller,
+        })
+    }
+
+    /// It returns the prize equity of the pusher folding.
+    ///
+    pub fn fold_ev(&self, prizes: &[f64]) -> Result<f64, IcmError> {
+        let mut stacks = self.after_blinds();
+        stacks[self.caller] += self.dead();
+        Ok(Icm::equities(&stacks, prizes)?[self.pusher])
+    }
+
+    /// It returns the prize equity of the pusher going all-in, the caller calling with the given probability,
+    /// and the pusher then winning with the given equity.
+    ///
+    pub fn push_ev(&self, prizes: &[f64], call: f64, equity: f64) -> Result<f64, IcmError> {
+        let mut stacks = self.after_blinds();
+        stacks[self.pusher] += self.dead();
+        let folded = Icm::equities(&stacks, prizes)?[self.pusher];
+
+        let (won, lost) = self.showdowns();
+        let won = Icm::equities(&won, prizes)?[self.pusher];
+        let lost = Icm::equities(&lost, prizes)?[self.pusher];
+        Ok((1.0 - call) * folded + call * (equity * won + (1.0 - equity) * lost))
+    }
+
+    /// It returns the prize equity of the pusher going all-in with the `hole` cards, the caller calling with the given `range`:
+    /// the chance to be called is the share of the holdings left by the hole cards that are in the range,
+    /// and the equity against it is estimated with [Icm::preflop_equity].
+    ///
+    pub fn push_ev_against<R: Rng>(
+        &self,
+        prizes: &[f64],
+        hole: &[Card; 2],
+        range: &[[Card; 2]],
+        samples: usize,
+        rng: &mut R,
+    ) -> Result<f64, IcmError> {
+        let open = range.iter().filter(|h| !h.iter().any(|c| hole.contains(c))).count();
+        // the holdings made of the 50 cards left
+        let call = open as f64 / 1225.0;
+        let equity = Icm::preflop_equity(hole, range, samples, rng)?;
+        self.push_ev(prizes, call, equity)
+    }
+
+    /// It returns the prize equity of the caller calling the shove, winning with the given equity, and the one of folding.
+    ///
+    pub fn call_ev(&self, prizes: &[f64], equity: f64) -> Result<(f64, f64), IcmError> {
+        let (won, lost) = self.showdowns();
+        let called = equity * Icm::equities(&lost, prizes)?[self.caller] + (1.0 - equity) * Icm::equities(&won, prizes)?[self.caller];
+        let mut stacks = self.after_blinds();
+        stacks[self.pusher] += self.dead();
+        Ok((called, Icm::equities(&stacks, prizes)?[self.caller]))
+    }
+
+    /// function that returns the stacks once the blinds and antes are posted
+    ///
+    fn after_blinds(&self) -> Vec<u64> {
+        self.stacks.iter().zip(&self.posted).map(|(s, p)| s - p).collect()
+    }
+
+    /// function that returns the chips posted in the pot
+    ///
+    fn dead(&self) -> u64 {
+        self.posted.iter().sum()
+    }
+
+    /// function that returns the stacks after the all-in is called, when the pusher wins and when the pusher loses
+    ///
+    fn showdowns(&self) -> (Vec<u64>, Vec<u64>) {
+        let (p, c) = (self.pusher, self.caller);
+        let covered = self.stacks[p].min(self.stacks[c]);
+        // the two players put in the covered amount each, a post above it (i.e. into a short big blind) going back
+        let pot = self.dead() - self.posted[p] - self.posted[c] + 2 * covered;
+
+        let mut stacks = self.after_blinds();
+        stacks[p] = self.stacks[p] - covered;
+        stacks[c] = self.stacks[c] - covered;
+        let mut won = stacks.clone();
+        won[p] += pot;
+        let mut lost = stacks;
+        lost[c] += pot;
+        (won, lost)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::{Icm, IcmError, PushFold};
+    use crate::card::Card;
+    use crate::{cards, newcard};
+
+    #[test]
+    fn icm_exact() {
+        let prizes = [50.0, 30.0, 20.0];
+        // equal stacks share the prize pool equally
+        let equities = Icm::equities(&[1000, 1000, 1000, 1000], &prizes).unwrap();
+        assert!(equities.iter().all(|e| (e - 25.0).abs() < 1e-9));
+
+        // the classic example: 5000, 3000 and 2000 chips
+        let equities = Icm::equities(&[5000, 3000, 2000], &prizes).unwrap();
+        assert!((equities[0] - 38.3929).abs() < 1e-3, "{equities:?}");
+        assert!((equities[1] - 32.75).abs() < 1e-3, "{equities:?}");
+        assert!((equities[2] - 28.8571).abs() < 1e-3, "{equities:?}");
+        assert!((equities.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+
+        // a busted player gets the last prize
+        let equities = Icm::equities(&[5000, 5000, 0], &prizes).unwrap();
+        assert_eq!(equities, vec![40.0, 40.0, 20.0]);
+
+        assert_eq!(Icm::equities(&[0, 0], &prizes), Err(IcmError::NoChips));
+        assert_eq!(Icm::equities(&[1; 21], &prizes), Err(IcmError::TooManyPlayers(20)));
+    }
+
+    #[test]
+    fn icm_sampled() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let prizes = [50.0, 30.0, 20.0];
+        let exact = Icm::equities(&[5000, 3000, 2000, 1000], &prizes).unwrap();
+        let sampled = Icm::sample_equities(&[5000, 3000, 2000, 1000], &prizes, 20_000, &mut rng).unwrap();
+        for (e, s) in exact.iter().zip(&sampled) {
+            assert!((e - s).abs() < 1.0, "{exact:?} {sampled:?}");
+        }
+
+        // a large field
+        let stacks: Vec<u64> = (1..=100).collect();
+        let sampled = Icm::sample_equities(&stacks, &[500.0, 300.0, 200.0], 2_000, &mut rng).unwrap();
+        assert!((sampled.iter().sum::<f64>() - 1000.0).abs() < 1e-6);
+        assert!(sampled[99] > sampled[0]);
+
+        // the busted players share the positions left, as in the exact model
+        let sampled = Icm::sample_equities(&[5000, 5000, 0, 0], &prizes, 20_000, &mut rng).unwrap();
+        assert!((sampled[2] - 10.0).abs() < 0.5 && (sampled[3] - 10.0).abs() < 0.5, "{sampled:?}");
+        assert_eq!(Icm::sample_equities(&[5000, 3000], &prizes, 0, &mut rng), Err(IcmError::NoSamples));
+    }
+
+    #[test]
+    fn icm_push_fold() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let equity = Icm::preflop_equity(&cards!["Ah", "Ad"], &[cards!["Kc", "Kd"]], 5_000, &mut rng).unwrap();
+        assert!((equity - 0.82).abs() < 0.03, "{equity}");
+
+        // the small blind shoves 10 big blinds into the big blind, on the bubble of three players paid
+        let push = PushFold::new(vec![1000, 1000, 4000, 4000], vec![50, 100, 0, 0], 0, 1).unwrap();
+        let prizes = [50.0, 30.0, 20.0];
+        let fold = push.fold_ev(&prizes).unwrap();
+        // never called, the shove steals the blinds
+        assert!(push.push_ev(&prizes, 0.0, 0.0).unwrap() > fold);
+        // always called by a better hand, it's a disaster
+        assert!(push.push_ev(&prizes, 1.0, 0.3).unwrap() < fold);
+        // with 48% equity the call wins chips (960 against 900 on average), but the risk of busting on the bubble makes it a mistake
+        let (call, fold) = push.call_ev(&prizes, 0.48).unwrap();
+        assert!(call < fold);
+        let (call, fold) = push.call_ev(&prizes, 0.55).unwrap();
+        assert!(call > fold);
+
+        // aces are a shove against any calling range
+        let range = [cards!["Kc", "Kd"], cards!["Qc", "Qd"], cards!["Ac", "Kc"]];
+        let ev = push.push_ev_against(&prizes, &cards!["Ah", "As"], &range, 2_000, &mut rng).unwrap();
+        assert!(ev > push.fold_ev(&prizes).unwrap());
+
+        // the big blind is all-in for 40 chips, less than the small blind: called, 10 of the 50 posted go back to the pusher
+        let push = PushFold::new(vec![1000, 40, 4000, 4000], vec![50, 40, 0, 0], 0, 1).unwrap();
+        assert_eq!(push.showdowns(), (vec![1040, 0, 4000, 4000], vec![960, 80, 4000, 4000]));
+        assert!(push.push_ev(&prizes, 1.0, 0.5).unwrap() > 0.0);
+
+        assert_eq!(PushFold::new(vec![1000, 1000], vec![50, 100], 0, 0), Err(IcmError::InvalidPlayers));
+        assert_eq!(PushFold::new(vec![1000, 1000], vec![50, 100], 0, 2), Err(IcmError::InvalidPlayers));
+        assert_eq!(PushFold::new(vec![1000, 1000], vec![50], 0, 1), Err(IcmError::InvalidPosted));
+        assert_eq!(PushFold::new(vec![1000, 40], vec![50, 100], 0, 1), Err(IcmError::InvalidPosted));
+        assert_eq!(Icm::preflop_equity(&cards!["Ah", "Ad"], &[], 0, &mut rng), Err(IcmError::NoSamples));
+    }
+}
@@ -0,0 +1,162 @@
+use std::cmp::Reverse;
+
+use strum::IntoEnumIterator;
+
+use crate::card::{Card, Suit};
+
+/// The [`Isomorphism`] struct. It maps a situation, made of hole cards and a board, to a canonical representative under suit permutations.
+///
+/// Two situations are isomorphic when one can be turned into the other by renaming the suits (i.e. A♥️ K♥️ and A♠️ K♠️ preflop),
+/// so they share every property that doesn't depend on the suits themselves (equities, hand strength, abstraction buckets...).
+///
+pub struct Isomorphism;
+
+impl Isomorphism {
+    /// It returns the canonical representative of the situation, with the cards of each group sorted by descending value.
+    ///
+    /// The representative is the isomorphic variant whose cards, hole cards first, list the suits in the order they're declared by [`Suit`],
+    /// so the first suit met is always [`Suit::Hearts`], the second [`Suit::Diamonds`] and so on.
+    ///
+    pub fn canonical(hole: &[Card], board: &[Card]) -> (Vec<Card>, Vec<Card>) {
+        Self::permutations()
+            .iter()
+            .map(|perm| (Self::apply(perm, hole), Self::apply(perm, board)))
+            .min_by_key(|(hole, board)| Self::key(hole, board))
+            .unwrap()
+    }
+
+    /// It returns the number of distinct situations isomorphic to the given one, itself included.
+    ///
+    /// i.e. 4 for a suited preflop hand, 6 for a pocket pair, 12 for an offsuit hand.
+    ///
+    pub fn variants(hole: &[Card], board: &[Card]) -> usize {
+        let mut keys: Vec<_> = Self::permutations()
+            .iter()
+            .map(|perm| Self::key(&Self::apply(perm, hole), &Self::apply(perm, board)))
+            .collect();
+        keys.sort();
+        keys.dedup();
+        keys.len()
+    }
+
+    /// It tells if two situations are the same up to a renaming of the suits.
+    ///
+    pub fn is_isomorphic(hole: &[Card], board: &[Card], other_hole: &[Card], other_board: &[Card]) -> bool {
+        Self::canonical(hole, board) == Self::canonical(other_hole, other_board)
+    }
+
+    /// function that lists the 24 permutations of the 4 suits, as the suit each [Suit] is renamed to
+    ///
+    fn permutations() -> Vec<[Suit; 4]> {
+        let suits: Vec<Suit> = Suit::iter().collect();
+        let mut perms = Vec::with_capacity(24);
+        for a in &suits {
+            for b in suits.iter().filter(|s| ![a].contains(s)) {
+                for c in suits.iter().filter(|s| ![a, b].contains(s)) {
+                    for d in suits.iter().filter(|s| ![a, b, c].contains(s)) {
+                        perms.push([*a, *b, *c, *d]);
+                    }
+                }
+            }
+        }
+        perms
+    }
+
+    /// function that renames the suits of the given cards, and sorts them
+    ///
+    fn apply(perm: &[Suit; 4], cards: &[Card]) -> Vec<Card> {
+        let mut cards: Vec<Card> = cards
+            .iter()
+            .map(|c| Card::new(c.val, perm[c.suit as usize]))
+            .collect();
+        cards.sort_by_key(|c| (Reverse(c.val), c.suit as usize));
+        cards
+    }
+
+    /// function that builds a totally ordered key out of sorted hole cards and board
+    ///
+    fn key(hole: &[Card], board: &[Card]) -> Vec<(Reverse<u8>, usize)> {
+        hole.iter()
+            .chain(board)
+            .map(|c| (Reverse(c.val), c.suit as usize))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Isomorphism;
+    use crate::card::{Card, Deck, Suit};
+    use crate::newcard;
+
+    #[test]
+    fn canonical_preflop() {
+        let (hole, board) = Isomorphism::canonical(&[newcard!["Ks"], newcard!["As"]], &[]);
+        assert_eq!(hole, vec![Card::new(14, Suit::Hearts), Card::new(13, Suit::Hearts)]);
+        assert!(board.is_empty());
+
+        assert!(Isomorphism::is_isomorphic(
+            &[newcard!["Ac"], newcard!["Kd"]],
+            &[],
+            &[newcard!["Kh"], newcard!["As"]],
+            &[]
+        ));
+        assert!(!Isomorphism::is_isomorphic(
+            &[newcard!["Ac"], newcard!["Kc"]],
+            &[],
+            &[newcard!["Kh"], newcard!["As"]],
+            &[]
+        ));
+    }
+
+    #[test]
+    fn canonical_with_board() {
+        let a = Isomorphism::canonical(
+            &[newcard!["Ah"], newcard!["Kd"]],
+            &[newcard!["2h"], newcard!["7h"], newcard!["9c"]],
+        );
+        let b = Isomorphism::canonical(
+            &[newcard!["As"], newcard!["Kc"]],
+            &[newcard!["9h"], newcard!["2s"], newcard!["7s"]],
+        );
+        assert_eq!(a, b);
+        // the flush draw is on the same suit as the ace, not on the king
+        let c = Isomorphism::canonical(
+            &[newcard!["As"], newcard!["Kc"]],
+            &[newcard!["9h"], newcard!["2c"], newcard!["7c"]],
+        );
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn variants_preflop() {
+        assert_eq!(Isomorphism::variants(&[newcard!["Ah"], newcard!["Kh"]], &[]), 4);
+        assert_eq!(Isomorphism::variants(&[newcard!["Ah"], newcard!["Kd"]], &[]), 12);
+        assert_eq!(Isomorphism::variants(&[newcard!["Ah"], newcard!["Ad"]], &[]), 6);
+        assert_eq!(
+            Isomorphism::variants(&[newcard!["Ah"], newcard!["Ad"]], &[newcard!["2c"], newcard!["2s"], newcard!["3h"]]),
+            12
+        );
+        assert_eq!(
+            Isomorphism::variants(&[newcard!["Ah"], newcard!["Ad"]], &[newcard!["2c"], newcard!["3s"], newcard!["4h"]]),
+            24
+        );
+    }
+
+    #[test]
+    fn variants_cover_every_starting_hand() {
+        let cards = Deck::create_ordered_deck().remaining(&[]);
+        let mut classes = Vec::new();
+        for i in 0..cards.len() {
+            for j in i + 1..cards.len() {
+                let canonical = Isomorphism::canonical(&[cards[i], cards[j]], &[]);
+                if !classes.contains(&canonical) {
+                    classes.push(canonical);
+                }
+            }
+        }
+        assert_eq!(classes.len(), 169);
+        let total: usize = classes.iter().map(|(hole, board)| Isomorphism::variants(hole, board)).sum();
+        assert_eq!(total, 1326);
+    }
+}
@@ -1,3 +1,49 @@
 pub mod card;
 
 pub mod match_evaluator;
+
+pub mod strength;
+
+pub mod isomorphism;
+
+pub mod omaha;
+
+pub mod lowball;
+
+pub mod showdown;
+
+pub mod stud;
+
+pub mod badugi;
+
+pub mod wild;
+
+pub mod video_poker;
+
+pub mod draw;
+
+pub mod three_card;
+
+pub mod ofc;
+
+pub mod pai_gow;
+
+pub mod caribbean_stud;
+
+pub mod ultimate_holdem;
+
+pub mod game;
+
+pub mod pot;
+
+pub mod tournament;
+
+pub mod icm;
+
+pub mod nash;
+
+pub mod cfr;
+
+pub mod player;
+
+pub mod arena;
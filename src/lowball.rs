@@ -0,0 +1,252 @@
+use std::cmp::Ordering;
+
+use crate::card::{Card, Hand};
+use crate::match_evaluator::{MatchHandEvaluator, Rank};
+
+/// The [`LowRank`] struct. It's the value of a hand in lowball games, where the weakest hand wins: its [`Rank`] plus its kickers.
+///
+/// The kickers are the five card values grouped by multiplicity and then by value, with the Ace counting as 1 in Ace-to-Five lowball and as 14 in Deuce-to-Seven.
+/// A better low hand has a weaker [`Rank`] or, for the same [`Rank`], lower kickers. As for [`Rank`], a better [`LowRank`] compares as less.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct LowRank {
+    pub rank: Rank,
+    pub kickers: [u8; 5],
+}
+
+impl PartialEq for LowRank {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for LowRank {}
+
+impl PartialOrd for LowRank {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LowRank {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .rank
+            .position()
+            .cmp(&self.rank.position())
+            .then_with(|| self.kickers.cmp(&other.kickers))
+    }
+}
+
+impl LowRank {
+    /// It tells if the hand qualifies as a low hand with no card above `max`, i.e. 8 for eight-or-better split games.
+    ///
+    pub fn qualifies(&self, max: u8) -> bool {
+        matches!(self.rank, Rank::HighCard(_)) && self.kickers[0] <= max
+    }
+}
+
+/// The [`LowballEvaluator`] struct. It evaluates hands for lowball games, either Ace-to-Five or Deuce-to-Seven.
+///
+pub struct LowballEvaluator;
+
+impl LowballEvaluator {
+    /// It evaluates the Ace-to-Five [LowRank] of a [Hand], used by Razz and by the low half of Hi-Lo split games.
+    ///
+    /// Straights and flushes don't count and the Ace is always low, so A 2 3 4 5 (the wheel) is the best possible hand.
+    /// Pairs, trips and so on do count against the hand, and only take their [Rank] among [Rank::OnePair], [Rank::TwoPair],
+    /// [Rank::ThreeOfAKind], [Rank::FullHouse] and [Rank::FourOfAKind]. Otherwise the rank is a [Rank::HighCard] of the highest card, Ace being the lowest one.
+    ///
+    pub fn ace_to_five(hand: &Hand) -> LowRank {
+        let low = |c: &Card| if c.val == 14 { 1 } else { c.val };
+        let cards = hand.get_hand_slice();
+        let vals = cards.map(|c| low(&c));
+        let count = |v: u8| vals.iter().filter(|k| **k == v).count();
+        let mut counts: Vec<usize> = vals.iter().map(|k| count(*k)).collect();
+        let mut kickers = vals;
+        kickers.sort_by(|a, b| count(*b).cmp(&count(*a)).then(b.cmp(a)));
+        counts.sort_by(|a, b| b.cmp(a));
+
+        let rank = match counts.as_slice() {
+            [4, ..] => Rank::FourOfAKind,
+            [3, 3, 3, 2, 2] => Rank::FullHouse,
+            [3, ..] => Rank::ThreeOfAKind,
+            [2, 2, 2, 2, 1] => Rank::TwoPair,
+            [2, ..] => Rank::OnePair,
+            _ => Rank::HighCard(*cards.iter().find(|c| low(c) == kickers[0]).unwrap()),
+        };
+        LowRank { rank, kickers }
+    }
+
+    /// It evaluates the best Ace-to-Five [LowRank] that can be made with any 5 of the given cards (i.e. the 7 cards of Razz).
+    ///
+    /// # Panics
+    ///
+    /// If less than 5 cards are given.
+    ///
+    pub fn best_ace_to_five(cards: &[Card]) -> LowRank {
+        Self::best(cards, Self::ace_to_five)
+    }
+
+    /// It evaluates the best Ace-to-Five low of the given cards that qualifies as eight-or-better, if there's any.
+    ///
+    pub fn eight_or_better(cards: &[Card]) -> Option<LowRank> {
+        Some(Self::best_ace_to_five(cards)).filter(|low| low.qualifies(8))
+    }
+
+    /// It evaluates the Deuce-to-Seven [LowRank] of a [Hand], used by 2-7 Single Draw and Triple Draw.
+    ///
+    /// The hand is classified as a high hand by the [MatchHandEvaluator], the weakest one winning. The Ace is always high,
+    /// so A 2 3 4 5 is not a straight but an Ace high, and straights and flushes count against the hand: 7 5 4 3 2 offsuit is the best possible hand.
+    ///
+    pub fn deuce_to_seven(hand: &Hand) -> LowRank {
+        let mut hand = hand.clone();
+        let score = MatchHandEvaluator::score(&mut hand);
+        let cards = hand.get_hand_slice();
+        match (score.rank, cards.map(|c| c.val)) {
+            // no wheel, the Ace plays high
+            (Rank::Straight, kickers @ [14, 5, 4, 3, 2]) => LowRank {
+                rank: Rank::HighCard(cards[0]),
+                kickers,
+            },
+            (Rank::StraightFlush, kickers @ [14, 5, 4, 3, 2]) => LowRank {
+                rank: Rank::Flush,
+                kickers,
+            },
+            (rank, _) => LowRank {
+                rank,
+                kickers: score.kickers,
+            },
+        }
+    }
+
+    /// It evaluates the best Deuce-to-Seven [LowRank] that can be made with any 5 of the given cards.
+    ///
+    /// # Panics
+    ///
+    /// If less than 5 cards are given.
+    ///
+    pub fn best_deuce_to_seven(cards: &[Card]) -> LowRank {
+        Self::best(cards, Self::deuce_to_seven)
+    }
+
+    /// function that evaluates every 5 cards combination of the given cards and keeps the best low
+    ///
+    fn best(cards: &[Card], eval: fn(&Hand) -> LowRank) -> LowRank {
+        assert!(cards.len() >= 5, "at least 5 cards are needed to make a hand");
+        let n = cards.len();
+        let mut best: Option<LowRank> = None;
+        for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    for d in c + 1..n {
+                        for e in d + 1..n {
+                            let low = eval(&Hand::new([cards[a], cards[b], cards[c], cards[d], cards[e]]));
+                            if best.is_none_or(|best| low < best) {
+                                best = Some(low);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        best.unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LowballEvaluator;
+    use crate::card::{Card, Hand};
+    use crate::match_evaluator::Rank;
+    use crate::{hand, newcard};
+
+    #[test]
+    fn ace_to_five_ranks() {
+        // the wheel is the best low, straight and flush don't count
+        let wheel = LowballEvaluator::ace_to_five(&hand!["5d", "4d", "3d", "2d", "Ad"]);
+        assert_eq!(wheel.rank, Rank::HighCard(newcard!["5d"]));
+        assert_eq!(wheel.kickers, [5, 4, 3, 2, 1]);
+
+        let pair = LowballEvaluator::ace_to_five(&hand!["Ad", "Ah", "3c", "4d", "5s"]);
+        assert_eq!(pair.rank, Rank::OnePair);
+        assert_eq!(pair.kickers, [1, 1, 5, 4, 3]);
+
+        assert_eq!(LowballEvaluator::ace_to_five(&hand!["Ad", "Ah", "3c", "3d", "5s"]).rank, Rank::TwoPair);
+        assert_eq!(LowballEvaluator::ace_to_five(&hand!["Ad", "Ah", "Ac", "3d", "5s"]).rank, Rank::ThreeOfAKind);
+        assert_eq!(LowballEvaluator::ace_to_five(&hand!["Ad", "Ah", "Ac", "5d", "5s"]).rank, Rank::FullHouse);
+        assert_eq!(LowballEvaluator::ace_to_five(&hand!["Ad", "Ah", "Ac", "As", "5s"]).rank, Rank::FourOfAKind);
+    }
+
+    #[test]
+    fn ace_to_five_comparison() {
+        let low = |hand: Hand| LowballEvaluator::ace_to_five(&hand);
+        assert!(low(hand!["5d", "4d", "3d", "2d", "Ad"]) < low(hand!["6d", "4c", "3d", "2d", "Ad"]));
+        assert!(low(hand!["6d", "4c", "3d", "2d", "Ad"]) < low(hand!["6d", "5c", "3d", "2d", "Ad"]));
+        assert!(low(hand!["8d", "7c", "6d", "5d", "Ah"]) < low(hand!["8d", "7c", "6d", "5d", "4d"]));
+        assert!(low(hand!["Kd", "Qc", "Jd", "9d", "8d"]) < low(hand!["2d", "2c", "3d", "4d", "5d"]));
+        assert!(low(hand!["2d", "2c", "3d", "4d", "5d"]) < low(hand!["Ad", "Ac", "3d", "3h", "5d"]));
+        assert_eq!(low(hand!["7d", "5c", "4d", "3d", "2d"]), low(hand!["7h", "5h", "4s", "3c", "2h"]));
+    }
+
+    #[test]
+    fn ace_to_five_eight_or_better() {
+        let cards = [newcard!["Ah"], newcard!["2h"], newcard!["Kd"], newcard!["Qc"], newcard!["7d"], newcard!["8s"], newcard!["5c"]];
+        let low = LowballEvaluator::eight_or_better(&cards).unwrap();
+        assert_eq!(low.kickers, [8, 7, 5, 2, 1]);
+
+        let cards = [newcard!["Ah"], newcard!["2h"], newcard!["Kd"], newcard!["Qc"], newcard!["7d"], newcard!["9s"], newcard!["5c"]];
+        assert_eq!(LowballEvaluator::eight_or_better(&cards), None);
+        assert_eq!(LowballEvaluator::best_ace_to_five(&cards).kickers, [9, 7, 5, 2, 1]);
+
+        // a paired low never qualifies
+        let cards = [newcard!["Ah"], newcard!["2h"], newcard!["2d"], newcard!["3c"], newcard!["3d"]];
+        assert_eq!(LowballEvaluator::eight_or_better(&cards), None);
+    }
+
+    #[test]
+    fn ace_to_five_razz() {
+        // the pairs are discarded out of seven cards
+        let cards = [newcard!["Ah"], newcard!["Ad"], newcard!["3d"], newcard!["3c"], newcard!["4d"], newcard!["6s"], newcard!["Kc"]];
+        let low = LowballEvaluator::best_ace_to_five(&cards);
+        assert_eq!(low.rank, Rank::HighCard(newcard!["Kc"]));
+        assert_eq!(low.kickers, [13, 6, 4, 3, 1]);
+    }
+
+    #[test]
+    fn deuce_to_seven_ranks() {
+        let number_one = LowballEvaluator::deuce_to_seven(&hand!["7d", "5c", "4d", "3d", "2d"]);
+        assert_eq!(number_one.rank, Rank::HighCard(newcard!["7d"]));
+        assert_eq!(number_one.kickers, [7, 5, 4, 3, 2]);
+
+        // no wheel, the Ace is high
+        let ace_high = LowballEvaluator::deuce_to_seven(&hand!["Ad", "5c", "4d", "3d", "2d"]);
+        assert_eq!(ace_high.rank, Rank::HighCard(newcard!["Ad"]));
+        assert_eq!(ace_high.kickers, [14, 5, 4, 3, 2]);
+        assert_eq!(LowballEvaluator::deuce_to_seven(&hand!["Ad", "5d", "4d", "3d", "2d"]).rank, Rank::Flush);
+
+        // straights and flushes count
+        assert_eq!(LowballEvaluator::deuce_to_seven(&hand!["6d", "5c", "4d", "3d", "2d"]).rank, Rank::Straight);
+        assert_eq!(LowballEvaluator::deuce_to_seven(&hand!["7d", "5d", "4d", "3d", "2d"]).rank, Rank::Flush);
+    }
+
+    #[test]
+    fn deuce_to_seven_comparison() {
+        let low = |hand: Hand| LowballEvaluator::deuce_to_seven(&hand);
+        assert!(low(hand!["7d", "5c", "4d", "3d", "2d"]) < low(hand!["7d", "6c", "4d", "3d", "2d"]));
+        assert!(low(hand!["8d", "6c", "5d", "4d", "2h"]) < low(hand!["8d", "6c", "5d", "4d", "3d"]));
+        assert!(low(hand!["Kd", "Qc", "Jd", "10d", "8h"]) < low(hand!["Ad", "5c", "4d", "3d", "2d"]));
+        assert!(low(hand!["Ad", "Kc", "Qd", "Jd", "9h"]) < low(hand!["2d", "2c", "4d", "3d", "5d"]));
+        assert!(low(hand!["2d", "2c", "4d", "3d", "5d"]) < low(hand!["6d", "5c", "4d", "3d", "2d"]));
+        assert!(low(hand!["6d", "5c", "4d", "3d", "2d"]) < low(hand!["7d", "5d", "4d", "3d", "2d"]));
+        assert!(low(hand!["Kd", "Kc", "Kh", "Ks", "2d"]) < low(hand!["Ad", "Kd", "Qd", "Jd", "10d"]));
+    }
+
+    #[test]
+    fn deuce_to_seven_best_of_seven() {
+        let cards = [newcard!["Ah"], newcard!["2h"], newcard!["3h"], newcard!["4h"], newcard!["5h"], newcard!["7h"], newcard!["8c"]];
+        let low = LowballEvaluator::best_deuce_to_seven(&cards);
+        assert_eq!(low.rank, Rank::HighCard(newcard!["8c"]));
+        assert_eq!(low.kickers, [8, 5, 4, 3, 2]);
+    }
+}
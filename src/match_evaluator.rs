@@ -1,8 +1,14 @@
+use std::cmp::Ordering;
+
 use crate::card::{Card, Hand, Suit};
 
 /// The [`Rank`] enum represents the standard poker hand ranks from highest to lowest
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy, strum_macros::Display)]
 pub enum Rank {
+    /// Five of a Kind
+    ///
+    /// Only possible with wild cards, five cards of the same rank. It beats any other hand, a Royal Flush included.
+    FiveOfAKind,
     ///  1. Royal Flush
     ///
     /// The highest rank possible, consisting of the Ace, King, Queen, Jack, and Ten all of the same suit.
@@ -46,6 +52,89 @@ pub enum Rank {
     None,
 }
 
+impl Rank {
+    /// It returns the position of the [Rank] in the standard ranking, from 0 for a [Rank::FiveOfAKind] to 10 for a [Rank::HighCard], regardless of its card.
+    ///
+    pub const fn position(&self) -> u8 {
+        match self {
+            Rank::FiveOfAKind => 0,
+            Rank::RoyalFlush => 1,
+            Rank::StraightFlush => 2,
+            Rank::FourOfAKind => 3,
+            Rank::FullHouse => 4,
+            Rank::Flush => 5,
+            Rank::Straight => 6,
+            Rank::ThreeOfAKind => 7,
+            Rank::TwoPair => 8,
+            Rank::OnePair => 9,
+            Rank::HighCard(_) => 10,
+            Rank::None => 11,
+        }
+    }
+}
+
+/// The [`Score`] of an evaluated hand: its [`Rank`] plus the card values breaking ties between hands of the same rank.
+///
+/// The kickers are the five card values grouped by multiplicity and then by value (i.e. K K K 8 8 becomes `[13, 13, 13, 8, 8]`),
+/// except for the lowest straight, where the Ace counts as 1 (`[5, 4, 3, 2, 1]` for the wheel, `[9, 8, 7, 6, 1]` in short-deck).
+/// As for [`Rank`], a stronger [`Score`] compares as less than a weaker one, following the [`Rules`] the hand was evaluated with.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct Score {
+    pub rank: Rank,
+    pub kickers: [u8; 5],
+    position: u8,
+}
+
+impl PartialEq for Score {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Score {}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.position
+            .cmp(&other.position)
+            .then_with(|| other.kickers.cmp(&self.kickers))
+    }
+}
+
+/// The [`Rules`] enum. It represents the hand rankings a [`Score`] is evaluated with.
+///
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Rules {
+    /// The standard rankings, as declared by [`Rank`].
+    #[default]
+    Standard,
+    /// Short-deck (6+) Hold'em, played with 36 cards from 6 to Ace.
+    ///
+    /// A Flush beats a Full House and A 6 7 8 9 is the lowest straight. Whether Three of a Kind beats a Straight depends on the house.
+    ShortDeck { trips_beat_straight: bool },
+}
+
+impl Rules {
+    /// It returns the position of a [Rank] in the ranking of these [Rules], from 0 for a [Rank::FiveOfAKind] to 10 for a [Rank::HighCard].
+    ///
+    pub const fn position(&self, rank: &Rank) -> u8 {
+        match (self, rank) {
+            (Rules::ShortDeck { .. }, Rank::Flush) => Rank::FullHouse.position(),
+            (Rules::ShortDeck { .. }, Rank::FullHouse) => Rank::Flush.position(),
+            (Rules::ShortDeck { trips_beat_straight: true }, Rank::ThreeOfAKind) => Rank::Straight.position(),
+            (Rules::ShortDeck { trips_beat_straight: true }, Rank::Straight) => Rank::ThreeOfAKind.position(),
+            (_, rank) => rank.position(),
+        }
+    }
+}
+
 pub struct MatchHandEvaluator;
 
 /// The core [MatchHandEvaluator] implementation.
@@ -53,8 +142,12 @@ pub struct MatchHandEvaluator;
 /// It examines the properties of a hand using array matching and struct matching to determine which rank the hand belongs to.
 /// 
 impl MatchHandEvaluator {
-    /// It evaluates the [Rank] of a [Hand]
+    /// It evaluates the [Rank] of a [Hand] with the standard rankings.
+    ///
+    /// The [Rules] aren't taken into account: use [MatchHandEvaluator::match_eval_with] to rank a hand of another game,
+    /// and [Rules::position] or [MatchHandEvaluator::score_with] to compare hands with its rankings.
     ///
+    #[allow(clippy::nonminimal_bool)]
     pub fn match_eval(hand: &mut Hand) -> Rank {
         // first let's sort the hand, that's the reason we need here a mutable reference
         hand.sort();
@@ -63,6 +156,11 @@ impl MatchHandEvaluator {
         let hand = hand.get_hand_slice();
 
         match hand {
+            [Card { val: v1, .. }, Card { val: v2, .. }, Card { val: v3, .. }, Card { val: v4, .. }, Card { val: v5, .. }]
+                if v1 == v2 && v2 == v3 && v3 == v4 && v4 == v5 =>
+            {
+                Rank::FiveOfAKind // only with wild cards
+            }
             [Card { suit: s1, val: 14 }, Card { suit: s2, val: 13 }, Card { suit: s3, val: 12 }, Card { suit: s4, val: 11 }, Card { suit: s5, val: 10 }]
                 if Self::suits(s1, s2, s3, s4, s5) =>
             {
@@ -123,6 +221,93 @@ impl MatchHandEvaluator {
         }
     }
 
+    /// It evaluates the [Rank] of a [Hand] with the given [Rules], i.e. A 6 7 8 9 is a [Rank::Straight] in short-deck.
+    ///
+    /// The [Rank] alone still compares by the standard rankings, see [Rules::position].
+    ///
+    pub fn match_eval_with(hand: &mut Hand, rules: Rules) -> Rank {
+        let rank = Self::match_eval(hand);
+        // special case with Ace as 5, the lowest short-deck straight
+        if matches!(rules, Rules::ShortDeck { .. }) && hand.get_hand_slice().map(|c| c.val) == [14, 9, 8, 7, 6] {
+            return if rank == Rank::Flush { Rank::StraightFlush } else { Rank::Straight };
+        }
+        rank
+    }
+
+    /// It evaluates the [Score] of a [Hand], i.e. its [Rank] together with its kickers.
+    ///
+    pub fn score(hand: &mut Hand) -> Score {
+        Self::score_with(hand, Rules::Standard)
+    }
+
+    /// It evaluates the [Score] of a [Hand] with the given [Rules].
+    ///
+    pub fn score_with(hand: &mut Hand, rules: Rules) -> Score {
+        let rank = Self::match_eval_with(hand, rules);
+        let cards = hand.get_hand_slice();
+
+        let kickers = if matches!(rules, Rules::ShortDeck { .. }) && cards.map(|c| c.val) == [14, 9, 8, 7, 6] {
+            [9, 8, 7, 6, 1]
+        } else {
+            Self::kickers(cards, &rank)
+        };
+        Score {
+            rank,
+            kickers,
+            position: rules.position(&rank),
+        }
+    }
+
+    /// It evaluates the best [Score] that can be made with any 5 of the given cards (i.e. the 7 cards of a Texas Hold'em showdown).
+    ///
+    /// # Panics
+    ///
+    /// If less than 5 cards are given.
+    ///
+    pub fn best_score(cards: &[Card]) -> Score {
+        Self::best_score_with(cards, Rules::Standard)
+    }
+
+    /// It evaluates the best [Score] that can be made with any 5 of the given cards, with the given [Rules].
+    ///
+    /// # Panics
+    ///
+    /// If less than 5 cards are given.
+    ///
+    pub fn best_score_with(cards: &[Card], rules: Rules) -> Score {
+        assert!(cards.len() >= 5, "at least 5 cards are needed to make a hand");
+        let n = cards.len();
+        let mut best: Option<Score> = None;
+        for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    for d in c + 1..n {
+                        for e in d + 1..n {
+                            let mut hand = Hand::new([cards[a], cards[b], cards[c], cards[d], cards[e]]);
+                            let score = Self::score_with(&mut hand, rules);
+                            if best.is_none_or(|best| score < best) {
+                                best = Some(score);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        best.unwrap()
+    }
+
+    /// function that lists the tie breaking values of a sorted hand of the given [Rank]
+    ///
+    fn kickers(hand: &[Card; 5], rank: &Rank) -> [u8; 5] {
+        let mut vals = hand.map(|c| c.val);
+        if matches!(rank, Rank::Straight | Rank::StraightFlush) && vals == [14, 5, 4, 3, 2] {
+            return [5, 4, 3, 2, 1];
+        }
+        let count = |v: u8| hand.iter().filter(|c| c.val == v).count();
+        vals.sort_by(|a, b| count(*b).cmp(&count(*a)).then(b.cmp(a)));
+        vals
+    }
+
     /// function that checks if 5 given suits are all the same (i.e. all [Suit::Hearts])
     ///
     fn suits(c0: &Suit, c1: &Suit, c2: &Suit, c3: &Suit, c4: &Suit) -> bool {
@@ -147,6 +332,8 @@ macro_rules! assert_rank {
 mod test {
     use super::MatchHandEvaluator;
     use super::Rank;
+    use super::Rules;
+    use super::Score;
     use crate::card::{Card, Hand};
     use crate::hand;
     use crate::newcard;
@@ -293,6 +480,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn rank_seq() {
         assert_eq!(MatchHandEvaluator::seq(14, 13, 12, 11, 10), true);
         assert_eq!(MatchHandEvaluator::seq(13, 14, 12, 11, 5), false);
@@ -302,6 +490,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn rank_suits() {
         use crate::card::Suit;
         // All same suit - should return true
@@ -320,6 +509,8 @@ mod test {
     fn rank_comparison_order() {
         // Test that hand rankings compare correctly using derived PartialOrd
         // Note: The enum is declared from best to worst, so better hands are "less than" in comparison
+        // Five of a Kind < Royal Flush (FiveOfAKind is better, declared first)
+        assert!(Rank::FiveOfAKind < Rank::RoyalFlush);
         // Royal Flush < Straight Flush (RoyalFlush is better, declared first)
         assert!(Rank::RoyalFlush < Rank::StraightFlush);
         // Straight Flush < Four of a Kind
@@ -340,6 +531,15 @@ mod test {
         assert!(Rank::OnePair < Rank::HighCard(newcard!["Ah"]));
     }
 
+    #[test]
+    fn rank_position() {
+        assert_eq!(Rank::FiveOfAKind.position(), 0);
+        assert_eq!(Rank::RoyalFlush.position(), 1);
+        assert_eq!(Rank::Flush.position(), 5);
+        assert_eq!(Rank::HighCard(newcard!["2h"]).position(), Rank::HighCard(newcard!["Ah"]).position());
+        assert!(Rank::OnePair.position() < Rank::HighCard(newcard!["Ah"]).position());
+    }
+
     #[test]
     fn rank_not_flush_or_straight() {
         // Should be three of a kind, not flush (different suits)
@@ -471,4 +671,120 @@ mod test {
         // Almost wheel - missing the 5
         assert_rank!(hand!["4c", "3h", "2d", "As", "Kh"], Rank::HighCard(newcard!["As"]));
     }
+
+    #[test]
+    fn score_kickers() {
+        let score = MatchHandEvaluator::score(&mut hand!["8s", "Kd", "8d", "Kh", "Kc"]);
+        assert_eq!(score.rank, Rank::FullHouse);
+        assert_eq!(score.kickers, [13, 13, 13, 8, 8]);
+
+        let score = MatchHandEvaluator::score(&mut hand!["3h", "Jc", "9d", "Js", "9h"]);
+        assert_eq!(score.kickers, [11, 11, 9, 9, 3]);
+
+        // the wheel is the lowest straight
+        let score = MatchHandEvaluator::score(&mut hand!["5c", "4h", "3d", "2s", "Ah"]);
+        assert_eq!(score.kickers, [5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn score_comparison() {
+        let score = |mut hand: Hand| MatchHandEvaluator::score(&mut hand);
+        // same rank, broken by kickers
+        assert!(score(hand!["Ad", "Ah", "Kc", "Qs", "Jd"]) < score(hand!["Ac", "As", "Kd", "Qh", "10d"]));
+        assert!(score(hand!["Kd", "Kh", "2c", "2s", "Ad"]) < score(hand!["Qd", "Qh", "Jc", "Js", "Ad"]));
+        assert!(score(hand!["6d", "5h", "4c", "3s", "2d"]) < score(hand!["5c", "4h", "3d", "2s", "Ah"]));
+        assert!(score(hand!["Ah", "Kd", "Qc", "Js", "8h"]) < score(hand!["Ad", "Kh", "Qd", "Jc", "7s"]));
+        // different suits, same values
+        assert_eq!(score(hand!["Ad", "Kh", "Qd", "Jc", "7s"]), score(hand!["Ac", "Kd", "Qs", "Jh", "7d"]));
+        // the rank comes first
+        assert!(score(hand!["2d", "2h", "3c", "4s", "5d"]) < score(hand!["Ad", "Kh", "Qd", "Jc", "9s"]));
+        // high cards compare by their kickers, not by the card carried by the rank
+        assert!(score(hand!["Ad", "Jh", "9c", "7s", "5d"]) < score(hand!["Kd", "Qh", "9d", "7c", "5s"]));
+        assert!(score(hand!["Kd", "Qh", "9d", "7c", "5s"]) > score(hand!["Ad", "Jh", "9c", "7s", "5d"]));
+    }
+
+    #[test]
+    fn score_best_of_seven() {
+        let cards = [
+            newcard!["Ah"],
+            newcard!["Kh"],
+            newcard!["2c"],
+            newcard!["Qh"],
+            newcard!["Jh"],
+            newcard!["7d"],
+            newcard!["10h"],
+        ];
+        assert_eq!(MatchHandEvaluator::best_score(&cards).rank, Rank::RoyalFlush);
+
+        let cards = [
+            newcard!["9c"],
+            newcard!["9d"],
+            newcard!["4h"],
+            newcard!["4s"],
+            newcard!["2c"],
+            newcard!["2d"],
+            newcard!["Kd"],
+        ];
+        let best: Score = MatchHandEvaluator::best_score(&cards);
+        assert_eq!(best.rank, Rank::TwoPair);
+        assert_eq!(best.kickers, [9, 9, 4, 4, 13]);
+    }
+
+    #[test]
+    fn score_short_deck_straights() {
+        let rules = Rules::ShortDeck { trips_beat_straight: false };
+        let score = |mut hand: Hand| MatchHandEvaluator::score_with(&mut hand, rules);
+
+        let low = score(hand!["Ah", "6d", "7c", "8s", "9h"]);
+        assert_eq!(low.rank, Rank::Straight);
+        assert_eq!(low.kickers, [9, 8, 7, 6, 1]);
+        assert!(score(hand!["10h", "6d", "7c", "8s", "9h"]) < low);
+        assert_eq!(score(hand!["Ah", "6h", "7h", "8h", "9h"]).rank, Rank::StraightFlush);
+
+        // not a straight with the standard rules
+        assert_eq!(MatchHandEvaluator::score(&mut hand!["Ah", "6d", "7c", "8s", "9h"]).rank, Rank::HighCard(newcard!["Ah"]));
+        assert_eq!(MatchHandEvaluator::match_eval(&mut hand!["Ah", "6d", "7c", "8s", "9h"]), Rank::HighCard(newcard!["Ah"]));
+        assert_eq!(MatchHandEvaluator::match_eval_with(&mut hand!["Ah", "6d", "7c", "8s", "9h"], rules), Rank::Straight);
+        assert_eq!(MatchHandEvaluator::match_eval_with(&mut hand!["Ah", "6h", "7h", "8h", "9h"], rules), Rank::StraightFlush);
+    }
+
+    #[test]
+    fn score_short_deck_rankings() {
+        let flush = hand!["Ah", "Jh", "9h", "8h", "7h"];
+        let full_house = hand!["Ac", "Ad", "As", "Kh", "Kc"];
+        let straight = hand!["10h", "6d", "7c", "8s", "9h"];
+        let trips = hand!["Qc", "Qd", "Qs", "Kh", "Ac"];
+
+        let standard = |mut hand: Hand| MatchHandEvaluator::score(&mut hand);
+        assert!(standard(full_house.clone()) < standard(flush.clone()));
+        assert!(standard(straight.clone()) < standard(trips.clone()));
+
+        let rules = Rules::ShortDeck { trips_beat_straight: false };
+        let short = |mut hand: Hand| MatchHandEvaluator::score_with(&mut hand, rules);
+        assert!(short(flush.clone()) < short(full_house.clone()));
+        assert!(short(straight.clone()) < short(trips.clone()));
+
+        let rules = Rules::ShortDeck { trips_beat_straight: true };
+        let short = |mut hand: Hand| MatchHandEvaluator::score_with(&mut hand, rules);
+        assert!(short(flush) < short(full_house));
+        assert!(short(trips) < short(straight));
+    }
+
+    #[test]
+    fn score_short_deck_best_of_eight() {
+        let cards = [
+            newcard!["Ah"],
+            newcard!["Ad"],
+            newcard!["Ac"],
+            newcard!["Kh"],
+            newcard!["Kd"],
+            newcard!["7h"],
+            newcard!["6h"],
+            newcard!["10h"],
+        ];
+        let rules = Rules::ShortDeck { trips_beat_straight: false };
+        // the flush, not the full house
+        assert_eq!(MatchHandEvaluator::best_score_with(&cards, rules).rank, Rank::Flush);
+        assert_eq!(MatchHandEvaluator::best_score(&cards).rank, Rank::FullHouse);
+    }
 }
@@ -0,0 +1,354 @@
+use std::fmt::Display;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use strum::IntoEnumIterator;
+
+use crate::card::{Card, Suit};
+use crate::icm::Icm;
+
+/// The [`StartingHand`] struct. One of the 169 classes of Hold'em hole cards, e.g. "AKs": the two values and whether they're suited.
+///
+/// The classes are laid out in the usual 13x13 chart, Aces first: the pairs on the diagonal,
+/// the suited hands above it (the row of the higher card) and the offsuit ones below it (the column of the higher card).
+///
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct StartingHand {
+    pub high: u8,
+    pub low: u8,
+    pub suited: bool,
+}
+
+impl StartingHand {
+    /// It returns the 169 starting hands in chart order, row by row.
+    ///
+    pub fn all() -> Vec<StartingHand> {
+        (0..169).map(Self::from_index).collect()
+    }
+
+    /// It returns the starting hand of the given chart cell, numbered row by row.
+    ///
+    pub fn from_index(index: usize) -> StartingHand {
+        let (row, col) = ((index / 13) as u8, (index % 13) as u8);
+        StartingHand {
+            high: 14 - row.min(col),
+            low: 14 - row.max(col),
+            suited: col > row,
+        }
+    }
+
+    /// It returns the starting hand of the given hole cards.
+    ///
+    pub fn of(hole: &[Card; 2]) -> StartingHand {
+        StartingHand {
+            high: hole[0].val.max(hole[1].val),
+            low: hole[0].val.min(hole[1].val),
+            suited: hole[0].suit == hole[1].suit && hole[0].val != hole[1].val,
+        }
+    }
+
+    /// It returns the index of the chart cell, numbered row by row.
+    ///
+    pub fn index(&self) -> usize {
+        let (high, low) = (usize::from(14 - self.high), usize::from(14 - self.low));
+        if self.suited {
+            high * 13 + low
+        } else {
+            low * 13 + high
+        }
+    }
+
+    /// It lists every combination of hole cards of the starting hand: 6 for a pair, 4 if suited, 12 if offsuit.
+    ///
+    pub fn combos(&self) -> Vec<[Card; 2]> {
+        let mut combos = vec![];
+        for first in Suit::iter() {
+            for second in Suit::iter() {
+                let suited = first == second;
+                let pair = self.high == self.low;
+                // a pair takes every two suits once
+                if (pair && first.order() < second.order()) || (!pair && suited == self.suited) {
+                    combos.push([Card::new(self.high, first), Card::new(self.low, second)]);
+                }
+            }
+        }
+        combos
+    }
+}
+
+impl Display for StartingHand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = |v: u8| match v {
+            14 => 'A',
+            13 => 'K',
+            12 => 'Q',
+            11 => 'J',
+            10 => 'T',
+            v => char::from(b'0' + v),
+        };
+        write!(f, "{}{}", value(self.high), value(self.low))?;
+        match (self.high == self.low, self.suited) {
+            (true, _) => Ok(()),
+            (false, true) => write!(f, "s"),
+            (false, false) => write!(f, "o"),
+        }
+    }
+}
+
+/// The [`PreflopEquities`] struct. The preflop all-in equity of every starting hand against every other, ties counting half.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct PreflopEquities {
+    /// The equity of the starting hand of index `i` against the one of index `j`, at `i * 169 + j`.
+    pub equities: Vec<f64>,
+}
+
+impl PreflopEquities {
+    /// It estimates the table running `samples` random boards for each matchup, with random combinations of the two starting hands.
+    ///
+    /// Every board is evaluated with [Icm::showdown], so a precise table takes a while: a few samples per matchup
+    /// are enough for charts, since the equities are averaged over whole ranges.
+    ///
+    pub fn sample<R: Rng>(samples: usize, rng: &mut R) -> PreflopEquities {
+        let mut equities = vec![0.5; 169 * 169];
+        for i in 0..169 {
+            for j in i + 1..169 {
+                let equity = Self::matchup(&StartingHand::from_index(i), &StartingHand::from_index(j), samples, rng);
+                equities[i * 169 + j] = equity;
+                equities[j * 169 + i] = 1.0 - equity;
+            }
+        }
+        PreflopEquities { equities }
+    }
+
+    /// It estimates the equity of a starting hand against another one, running `samples` random boards.
+    ///
+    pub fn matchup<R: Rng>(hero: &StartingHand, villain: &StartingHand, samples: usize, rng: &mut R) -> f64 {
+        let heroes = hero.combos();
+        let villains = villain.combos();
+        let mut won = 0.0;
+        let mut played = 0;
+        while played < samples {
+            let (ours, theirs) = (heroes.choose(rng).unwrap(), villains.choose(rng).unwrap());
+            if ours.iter().any(|c| theirs.contains(c)) {
+                continue;
+            }
+            won += Icm::showdown(ours, theirs, rng);
+            played += 1;
+        }
+        won / samples as f64
+    }
+
+    /// It returns the equity of a starting hand against another one.
+    ///
+    pub fn equity(&self, hero: &StartingHand, villain: &StartingHand) -> f64 {
+        self.equities[hero.index() * 169 + villain.index()]
+    }
+}
+
+/// The [`Chart`] struct. A 13x13 chart of the frequency each starting hand is played with, e.g. shoved or called.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Chart {
+    pub frequencies: [[f64; 13]; 13],
+}
+
+impl Chart {
+    /// It builds the chart of the frequencies of the 169 starting hands, in chart order.
+    ///
+    pub fn new(frequencies: &[f64]) -> Chart {
+        let mut chart = Chart {
+            frequencies: [[0.0; 13]; 13],
+        };
+        for (i, frequency) in frequencies.iter().enumerate().take(169) {
+            chart.frequencies[i / 13][i % 13] = *frequency;
+        }
+        chart
+    }
+
+    /// It returns the frequency of a starting hand.
+    ///
+    pub fn frequency(&self, hand: &StartingHand) -> f64 {
+        let index = hand.index();
+        self.frequencies[index / 13][index % 13]
+    }
+
+    /// It returns the share of the 1326 combinations of hole cards played, weighting each hand by its frequency.
+    ///
+    pub fn range(&self) -> f64 {
+        StartingHand::all()
+            .iter()
+            .map(|hand| self.frequency(hand) * hand.combos().len() as f64)
+            .sum::<f64>()
+            / 1326.0
+    }
+}
+
+impl Display for Chart {
+    /// The hands played at least half of the time are shown, the others as dots.
+    ///
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..13 {
+            for col in 0..13 {
+                let hand = StartingHand::from_index(row * 13 + col);
+                if self.frequencies[row][col] >= 0.5 {
+                    write!(f, "{:<4}", hand.to_string())?;
+                } else {
+                    write!(f, "{:<4}", ".")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// The [`HeadsUpNash`] struct. The push/fold Nash equilibrium of a heads-up hand: the small blind shoves or folds, the big blind calls or folds.
+///
+/// The blinds are 0.5 and 1, and `stack` is the effective stack in big blinds. Each player's strategy is the frequency
+/// each starting hand is shoved or called with, in chart order.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct HeadsUpNash {
+    pub stack: f64,
+    pub shove: Vec<f64>,
+    pub call: Vec<f64>,
+}
+
+impl HeadsUpNash {
+    /// It approximates the equilibrium by fictitious play: for the given iterations each player plays the best response
+    /// to the average strategy of the other, the strategies converging to the average of the best responses.
+    ///
+    /// The chance of each matchup accounts for the cards the hands block to each other.
+    ///
+    pub fn solve(stack: f64, equities: &PreflopEquities, iterations: usize) -> HeadsUpNash {
+        let hands = StartingHand::all();
+        let weights = Self::weights(&hands);
+        let mut nash = HeadsUpNash {
+            stack,
+            shove: vec![1.0; 169],
+            call: vec![0.0; 169],
+        };
+        for k in 1..=iterations {
+            let shove = nash.shove_response(equities, &weights);
+            let call = nash.call_response(equities, &weights);
+            let step = 1.0 / (k + 1) as f64;
+            for i in 0..169 {
+                nash.shove[i] += (shove[i] - nash.shove[i]) * step;
+                nash.call[i] += (call[i] - nash.call[i]) * step;
+            }
+        }
+        nash
+    }
+
+    /// It returns the chart of the small blind's shoves.
+    ///
+    pub fn shove_chart(&self) -> Chart {
+        Chart::new(&self.shove)
+    }
+
+    /// It returns the chart of the big blind's calls.
+    ///
+    pub fn call_chart(&self) -> Chart {
+        Chart::new(&self.call)
+    }
+
+    /// function that returns, for each pair of starting hands, the combinations of the second left by one combination of the first
+    ///
+    fn weights(hands: &[StartingHand]) -> Vec<f64> {
+        let combos: Vec<Vec<[Card; 2]>> = hands.iter().map(|h| h.combos()).collect();
+        let mut weights = vec![0.0; 169 * 169];
+        for i in 0..169 {
+            let hole = &combos[i][0];
+            for j in 0..169 {
+                weights[i * 169 + j] = combos[j].iter().filter(|c| !c.iter().any(|c| hole.contains(c))).count() as f64;
+            }
+        }
+        weights
+    }
+
+    /// function that returns the best response of the small blind to the calls of the big blind, shoving when it beats folding (-0.5)
+    ///
+    fn shove_response(&self, equities: &PreflopEquities, weights: &[f64]) -> Vec<f64> {
+        (0..169)
+            .map(|i| {
+                let (mut ev, mut total) = (0.0, 0.0);
+                for j in 0..169 {
+                    let w = weights[i * 169 + j];
+                    let called = (2.0 * equities.equities[i * 169 + j] - 1.0) * self.stack;
+                    ev += w * (self.call[j] * called + (1.0 - self.call[j]) * 1.0);
+                    total += w;
+                }
+                f64::from(u8::from(ev / total > -0.5))
+            })
+            .collect()
+    }
+
+    /// function that returns the best response of the big blind to the shoves of the small blind, calling when it beats folding (-1)
+    ///
+    fn call_response(&self, equities: &PreflopEquities, weights: &[f64]) -> Vec<f64> {
+        (0..169)
+            .map(|j| {
+                let gain: f64 = (0..169)
+                    .map(|i| {
+                        let called = (2.0 * equities.equities[j * 169 + i] - 1.0) * self.stack;
+                        weights[j * 169 + i] * self.shove[i] * (called + 1.0)
+                    })
+                    .sum();
+                f64::from(u8::from(gain > 0.0))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::{HeadsUpNash, PreflopEquities, StartingHand};
+    use crate::card::Card;
+    use crate::newcard;
+
+    #[test]
+    fn nash_starting_hands() {
+        let hands = StartingHand::all();
+        assert_eq!(hands.iter().map(|h| h.combos().len()).sum::<usize>(), 1326);
+        assert!(hands.iter().enumerate().all(|(i, h)| h.index() == i));
+        assert_eq!(hands[0].to_string(), "AA");
+        assert_eq!(hands[1].to_string(), "AKs");
+        assert_eq!(hands[13].to_string(), "AKo");
+        assert_eq!(hands[168].to_string(), "22");
+        assert_eq!(hands[4 * 13 + 12].to_string(), "T2s");
+
+        let hole: [Card; 2] = [newcard!["7d"], newcard!["Kd"]];
+        assert_eq!(StartingHand::of(&hole).to_string(), "K7s");
+    }
+
+    #[test]
+    fn nash_heads_up() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let aces = StartingHand::from_index(0);
+        let kings = StartingHand::from_index(14);
+        let equity = PreflopEquities::matchup(&aces, &kings, 2_000, &mut rng);
+        assert!((equity - 0.82).abs() < 0.03, "{equity}");
+
+        // a rough table is enough for the ranges
+        let equities = PreflopEquities::sample(2, &mut rng);
+        let seven_deuce = StartingHand::of(&[newcard!["7d"], newcard!["2c"]]);
+
+        let nash = HeadsUpNash::solve(20.0, &equities, 200);
+        let (shove, call) = (nash.shove_chart(), nash.call_chart());
+        assert!(shove.frequency(&aces) > 0.9);
+        assert!(call.frequency(&aces) > 0.9);
+        assert!(shove.frequency(&seven_deuce) < 0.5);
+        assert!(call.frequency(&seven_deuce) < 0.5);
+        // the big blind calls tighter than the small blind shoves
+        assert!(call.range() < shove.range());
+        assert!(shove.to_string().starts_with("AA  AKs"));
+
+        // with a short stack the small blind shoves nearly any two cards
+        let nash = HeadsUpNash::solve(2.0, &equities, 200);
+        assert!(nash.shove_chart().range() > 0.9, "{}", nash.shove_chart());
+    }
+}
@@ -0,0 +1,292 @@
+use std::cmp::{Ordering, Reverse};
+
+use crate::card::{Card, Hand};
+use crate::match_evaluator::{MatchHandEvaluator, Rank};
+
+/// The [`RowScore`] struct. The value of a row of an Open-face Chinese poker hand, comparable across the 3-card front and the 5-card rows.
+///
+/// The front only makes High Card, One Pair or Three of a Kind (no straights nor flushes), its kickers padded with zeros:
+/// i.e. Q Q 5 in front gives [12, 12, 5, 0, 0], which is beaten by Q Q 5 3 2 in the middle. As for [`crate::match_evaluator::Score`], a better row compares as less.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct RowScore {
+    pub rank: Rank,
+    pub kickers: [u8; 5],
+}
+
+impl PartialEq for RowScore {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for RowScore {}
+
+impl PartialOrd for RowScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RowScore {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank
+            .position()
+            .cmp(&other.rank.position())
+            .then_with(|| other.kickers.cmp(&self.kickers))
+    }
+}
+
+impl RowScore {
+    /// It evaluates the 3-card front row.
+    ///
+    pub fn front(cards: &[Card; 3]) -> RowScore {
+        let mut cards = *cards;
+        cards.sort_by_key(|c| Reverse(c.val));
+        let [v1, v2, v3] = cards.map(|c| c.val);
+        let (rank, kickers) = match (v1 == v2, v2 == v3) {
+            (true, true) => (Rank::ThreeOfAKind, [v1, v2, v3, 0, 0]),
+            (true, false) => (Rank::OnePair, [v1, v2, v3, 0, 0]),
+            (false, true) => (Rank::OnePair, [v2, v3, v1, 0, 0]),
+            (false, false) => (Rank::HighCard(cards[0]), [v1, v2, v3, 0, 0]),
+        };
+        RowScore { rank, kickers }
+    }
+
+    /// It evaluates a 5-card row, the middle or the back one.
+    ///
+    pub fn row(cards: &[Card; 5]) -> RowScore {
+        let score = MatchHandEvaluator::score(&mut Hand::new(*cards));
+        RowScore {
+            rank: score.rank,
+            kickers: score.kickers,
+        }
+    }
+}
+
+/// The [`OfcHand`] struct. A complete Open-face Chinese poker hand of 13 cards, set in three rows.
+///
+/// The back must be at least as strong as the middle, and the middle at least as strong as the front, otherwise the hand is fouled:
+/// it scores no royalties and loses every row.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct OfcHand {
+    pub front: [Card; 3],
+    pub middle: [Card; 5],
+    pub back: [Card; 5],
+}
+
+impl OfcHand {
+    /// It sets a hand with the given rows.
+    ///
+    pub fn new(front: [Card; 3], middle: [Card; 5], back: [Card; 5]) -> OfcHand {
+        OfcHand { front, middle, back }
+    }
+
+    /// It returns the [RowScore] of the front, middle and back rows.
+    ///
+    pub fn rows(&self) -> [RowScore; 3] {
+        [RowScore::front(&self.front), RowScore::row(&self.middle), RowScore::row(&self.back)]
+    }
+
+    /// It tells if the hand is fouled, i.e. a row is stronger than the one below it.
+    ///
+    pub fn is_fouled(&self) -> bool {
+        let [front, middle, back] = self.rows();
+        front < middle || middle < back
+    }
+
+    /// It returns the royalties (bonus points) of the hand, according to the standard tables:
+    ///
+    /// - front: 1 point for a pair of 6s up to 9 for a pair of Aces, 10 for trips of 2s up to 22 for trips of Aces;
+    /// - middle: Three of a Kind 2, Straight 4, Flush 8, Full House 12, Four of a Kind 20, Straight Flush 30, Royal Flush 50;
+    /// - back: Straight 2, Flush 4, Full House 6, Four of a Kind 10, Straight Flush 15, Royal Flush 25.
+    ///
+    /// A fouled hand scores no royalties.
+    ///
+    pub fn royalties(&self) -> u32 {
+        if self.is_fouled() {
+            return 0;
+        }
+        let [front, middle, back] = self.rows();
+        let front = match front.rank {
+            Rank::ThreeOfAKind => u32::from(front.kickers[0]) + 8,
+            Rank::OnePair if front.kickers[0] >= 6 => u32::from(front.kickers[0]) - 5,
+            _ => 0,
+        };
+        let middle = match middle.rank {
+            Rank::ThreeOfAKind => 2,
+            Rank::Straight => 4,
+            Rank::Flush => 8,
+            Rank::FullHouse => 12,
+            Rank::FourOfAKind => 20,
+            Rank::StraightFlush => 30,
+            Rank::RoyalFlush => 50,
+            _ => 0,
+        };
+        let back = match back.rank {
+            Rank::Straight => 2,
+            Rank::Flush => 4,
+            Rank::FullHouse => 6,
+            Rank::FourOfAKind => 10,
+            Rank::StraightFlush => 15,
+            Rank::RoyalFlush => 25,
+            _ => 0,
+        };
+        front + middle + back
+    }
+
+    /// It tells if the hand qualifies for Fantasyland: a pair of Queens or better in front, without fouling.
+    ///
+    pub fn fantasyland(&self) -> bool {
+        let front = RowScore::front(&self.front);
+        !self.is_fouled()
+            && match front.rank {
+                Rank::ThreeOfAKind => true,
+                Rank::OnePair => front.kickers[0] >= 12,
+                _ => false,
+            }
+    }
+
+    /// It tells if a player in Fantasyland stays there for the next hand: Three of a Kind in front,
+    /// a Full House or better in the middle, or Four of a Kind or better in the back, without fouling.
+    ///
+    pub fn stays_in_fantasyland(&self) -> bool {
+        let [front, middle, back] = self.rows();
+        !self.is_fouled()
+            && (front.rank == Rank::ThreeOfAKind
+                || middle.rank.position() <= Rank::FullHouse.position()
+                || back.rank.position() <= Rank::FourOfAKind.position())
+    }
+
+    /// It scores the hand against another one, returning the points won (negative when lost).
+    ///
+    /// Each row won is worth 1 point, and winning all three (a scoop) is worth 3 more. A fouled hand loses every row to a hand that isn't,
+    /// and two fouled hands tie. The difference of the royalties is added on top.
+    ///
+    pub fn against(&self, other: &OfcHand) -> i32 {
+        let rows: i32 = match (self.is_fouled(), other.is_fouled()) {
+            (true, true) => 0,
+            (true, false) => -3,
+            (false, true) => 3,
+            (false, false) => self
+                .rows()
+                .iter()
+                .zip(other.rows())
+                .map(|(mine, theirs)| match mine.cmp(&theirs) {
+                    Ordering::Less => 1,
+                    Ordering::Equal => 0,
+                    Ordering::Greater => -1,
+                })
+                .sum(),
+        };
+        let scoop = match rows {
+            3 => 3,
+            -3 => -3,
+            _ => 0,
+        };
+        rows + scoop + self.royalties() as i32 - other.royalties() as i32
+    }
+}
+
+/// It settles a table of Open-face Chinese poker, every player scoring against each of the others, returning the net points of each player.
+///
+pub fn settle(hands: &[OfcHand]) -> Vec<i32> {
+    hands
+        .iter()
+        .enumerate()
+        .map(|(i, hand)| {
+            hands
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other)| hand.against(other))
+                .sum()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{settle, OfcHand, RowScore};
+    use crate::card::Card;
+    use crate::match_evaluator::Rank;
+    use crate::newcard;
+
+    fn ofc(front: [&str; 3], middle: [&str; 5], back: [&str; 5]) -> OfcHand {
+        OfcHand::new(front.map(|c| newcard![c]), middle.map(|c| newcard![c]), back.map(|c| newcard![c]))
+    }
+
+    #[test]
+    fn ofc_front() {
+        let front = RowScore::front(&[newcard!["5h"], newcard!["Kd"], newcard!["5c"]]);
+        assert_eq!(front.rank, Rank::OnePair);
+        assert_eq!(front.kickers, [5, 5, 13, 0, 0]);
+
+        // no straights nor flushes in front
+        let front = RowScore::front(&[newcard!["Qh"], newcard!["Kh"], newcard!["Ah"]]);
+        assert_eq!(front.rank.position(), Rank::HighCard(newcard!["Ah"]).position());
+
+        // the same pair plays better in the middle with any kicker
+        let middle = RowScore::row(&[newcard!["Qh"], newcard!["Qd"], newcard!["5c"], newcard!["3d"], newcard!["2s"]]);
+        assert!(middle < RowScore::front(&[newcard!["Qs"], newcard!["Qc"], newcard!["5d"]]));
+        assert!(middle > RowScore::front(&[newcard!["Qs"], newcard!["Qc"], newcard!["6d"]]));
+
+        // the suits don't matter, even for the high card
+        let front = RowScore::front(&[newcard!["Ah"], newcard!["9c"], newcard!["4d"]]);
+        assert_eq!(front, RowScore::front(&[newcard!["As"], newcard!["9h"], newcard!["4c"]]));
+    }
+
+    #[test]
+    fn ofc_foul() {
+        let hand = ofc(["Ah", "Ad", "2c"], ["Kh", "Kd", "5c", "5s", "9h"], ["9c", "9d", "9s", "3c", "3d"]);
+        assert!(!hand.is_fouled());
+        // the middle beats the back
+        let hand = ofc(["Ah", "Ad", "2c"], ["9c", "9d", "9s", "3c", "3d"], ["Kh", "Kd", "5c", "5s", "9h"]);
+        assert!(hand.is_fouled());
+        assert_eq!(hand.royalties(), 0);
+        // the front beats the middle
+        let hand = ofc(["Ah", "Ad", "2c"], ["Kh", "Kd", "5c", "4s", "9h"], ["9c", "9d", "9s", "3c", "3d"]);
+        assert!(hand.is_fouled());
+    }
+
+    #[test]
+    fn ofc_royalties() {
+        // 66 in front, a flush in the middle, a full house in the back
+        let hand = ofc(["6h", "6d", "2c"], ["Kh", "Jh", "8h", "5h", "3h"], ["9c", "9d", "9s", "3c", "3d"]);
+        assert_eq!(hand.royalties(), 1 + 8 + 6);
+        // AAA in front, quads in the middle, a royal flush in the back
+        let hand = ofc(["Ah", "Ad", "Ac"], ["Kh", "Kd", "Kc", "Ks", "3h"], ["As", "Qs", "Js", "10s", "Ks"]);
+        assert_eq!(hand.royalties(), 22 + 20 + 25);
+        // 55 in front, trips in the middle, a straight in the back
+        let hand = ofc(["5h", "5d", "Ac"], ["8h", "8d", "8c", "2s", "3h"], ["5s", "4s", "3d", "2d", "Ad"]);
+        assert_eq!(hand.royalties(), 2 + 2);
+    }
+
+    #[test]
+    fn ofc_scoring() {
+        let strong = ofc(["6h", "6d", "2c"], ["Kh", "Jh", "8h", "5h", "3h"], ["9c", "9d", "9s", "3c", "3d"]);
+        let weak = ofc(["Ah", "Kd", "Qc"], ["Ks", "Kc", "8d", "4d", "2d"], ["Qh", "Qd", "7s", "7c", "4c"]);
+        // 3 rows, 3 for the scoop, 15 royalties
+        assert_eq!(strong.against(&weak), 3 + 3 + 15);
+        assert_eq!(weak.against(&strong), -21);
+
+        let fouled = ofc(["Ah", "Ad", "2c"], ["9c", "9d", "9s", "3c", "3d"], ["Kh", "Kd", "5c", "5s", "9h"]);
+        assert_eq!(weak.against(&fouled), 6);
+        assert_eq!(fouled.against(&fouled.clone()), 0);
+
+        assert_eq!(settle(&[strong, weak, fouled]), vec![21 + 21, -21 + 6, -21 - 6]);
+    }
+
+    #[test]
+    fn ofc_fantasyland() {
+        let queens = ofc(["Qh", "Qd", "2c"], ["Kh", "Kd", "5c", "5s", "9h"], ["9c", "9d", "9s", "3c", "3d"]);
+        assert!(queens.fantasyland());
+        assert!(!queens.stays_in_fantasyland());
+        let jacks = ofc(["Jh", "Jd", "2c"], ["Kh", "Kd", "5c", "5s", "9h"], ["9c", "9d", "9s", "3c", "3d"]);
+        assert!(!jacks.fantasyland());
+        let trips = ofc(["2h", "2d", "2c"], ["Kh", "Kd", "Kc", "5s", "9h"], ["9c", "9d", "9s", "3c", "3d"]);
+        assert!(trips.fantasyland());
+        assert!(trips.stays_in_fantasyland());
+    }
+}
@@ -0,0 +1,353 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use thiserror::Error;
+
+use crate::card::{Card, Deck, Hand};
+use crate::lowball::{LowRank, LowballEvaluator};
+use crate::match_evaluator::{MatchHandEvaluator, Score};
+use crate::showdown::Showdown;
+
+/// The [`OmahaEvaluator`] struct. It evaluates Omaha hands, where a player must use exactly two of the hole cards and three of the five board cards.
+///
+/// i.e. with A♥️ A♦️ K♣️ Q♣️ on a board of four hearts, the player has no flush at all, as only one heart can come from the hand.
+///
+/// The hole cards can be 4 (PLO), 5 (Big O) or 6 (6-card PLO), giving respectively 60, 100 or 150 candidate hands per player.
+///
+pub struct OmahaEvaluator;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum OmahaError {
+    #[error("The hole cards must be 4, 5 or 6")]
+    InvalidHole,
+
+    #[error("The board can't have more than 5 cards")]
+    InvalidBoard,
+
+    #[error("The board must have at least 3 cards to enumerate its runouts")]
+    IncompleteBoard,
+
+    #[error("At least one trial is needed")]
+    NoTrials,
+
+    #[error("There must be at least two players")]
+    NotEnoughPlayers,
+
+    #[error("The same card is dealt twice")]
+    DuplicateCard,
+}
+
+impl OmahaEvaluator {
+    /// It evaluates the best [Score] that can be made with exactly two of the `hole` cards and three of the `board` cards.
+    ///
+    /// # Panics
+    ///
+    /// If there are less than 2 hole cards.
+    ///
+    pub fn score(hole: &[Card], board: &[Card; 5]) -> Score {
+        Self::best(hole, board).1
+    }
+
+    /// It returns the best [Hand] that can be made with exactly two of the `hole` cards and three of the `board` cards, sorted in descending order.
+    ///
+    /// # Panics
+    ///
+    /// If there are less than 2 hole cards.
+    ///
+    pub fn best_hand(hole: &[Card], board: &[Card; 5]) -> Hand {
+        Self::best(hole, board).0
+    }
+
+    /// It evaluates the best eight-or-better Ace-to-Five [LowRank] that can be made with exactly two of the `hole` cards and three of the `board` cards, if any.
+    ///
+    pub fn low(hole: &[Card], board: &[Card; 5]) -> Option<LowRank> {
+        combinations(hole, 2)
+            .iter()
+            .flat_map(|pair| {
+                combinations(board, 3).into_iter().map(move |triplet| {
+                    LowballEvaluator::ace_to_five(&Hand::new([pair[0], pair[1], triplet[0], triplet[1], triplet[2]]))
+                })
+            })
+            .filter(|low| low.qualifies(8))
+            .min()
+    }
+
+    /// It settles the `pot` of an Omaha Hi-Lo (eight or better) showdown, with the players' hole cards in seat order from the left of the button.
+    ///
+    /// The pot is split between the best high hands and the best qualifying low hands, see [Showdown::hi_lo].
+    ///
+    pub fn hi_lo_showdown(players: &[&[Card]], board: &[Card; 5], pot: u64) -> Result<Showdown, OmahaError> {
+        Self::remaining(players, board)?;
+        let highs: Vec<Option<Score>> = players.iter().map(|hole| Some(Self::score(hole, board))).collect();
+        let lows: Vec<Option<LowRank>> = players.iter().map(|hole| Self::low(hole, board)).collect();
+        Ok(Showdown::hi_lo(pot, &highs, &lows))
+    }
+
+    /// It computes the all-in equity of each player, enumerating every possible runout of a flop, turn or river `board`.
+    ///
+    /// The equity is the share of the pot a player wins on average, ties being split among the winners.
+    ///
+    pub fn equity(players: &[&[Card]], board: &[Card]) -> Result<Vec<f64>, OmahaError> {
+        if board.len() < 3 {
+            // enumerating preflop would take millions of runouts, use sample_equity instead
+            return Err(OmahaError::IncompleteBoard);
+        }
+        let remaining = Self::remaining(players, board)?;
+        let runouts = combinations(&remaining, 5 - board.len());
+        Ok(Self::share(players, board, runouts.iter().map(|r| r.as_slice())))
+    }
+
+    /// It estimates the all-in equity of each player by sampling `trials` random runouts of the `board`, which can also be empty (preflop).
+    ///
+    pub fn sample_equity<R: Rng>(
+        players: &[&[Card]],
+        board: &[Card],
+        trials: usize,
+        rng: &mut R,
+    ) -> Result<Vec<f64>, OmahaError> {
+        if trials == 0 {
+            return Err(OmahaError::NoTrials);
+        }
+        let mut remaining = Self::remaining(players, board)?;
+        let missing = 5 - board.len();
+        let runouts: Vec<Vec<Card>> = (0..trials)
+            .map(|_| {
+                let (runout, _) = remaining.partial_shuffle(rng, missing);
+                runout.to_vec()
+            })
+            .collect();
+        Ok(Self::share(players, board, runouts.iter().map(|r| r.as_slice())))
+    }
+
+    /// function that scores all the candidate hands (pairs of hole cards times triplets of board cards) and keeps the best one
+    ///
+    fn best(hole: &[Card], board: &[Card; 5]) -> (Hand, Score) {
+        let mut best: Option<(Hand, Score)> = None;
+        for pair in combinations(hole, 2) {
+            for triplet in combinations(board, 3) {
+                let mut hand = Hand::new([pair[0], pair[1], triplet[0], triplet[1], triplet[2]]);
+                let score = MatchHandEvaluator::score(&mut hand);
+                if best.as_ref().is_none_or(|(_, best)| score < *best) {
+                    best = Some((hand, score));
+                }
+            }
+        }
+        best.expect("at least 2 hole cards are needed to make an Omaha hand")
+    }
+
+    /// function that validates a showdown and lists the cards left for the runouts
+    ///
+    fn remaining(players: &[&[Card]], board: &[Card]) -> Result<Vec<Card>, OmahaError> {
+        if players.len() < 2 {
+            return Err(OmahaError::NotEnoughPlayers);
+        }
+        if players.iter().any(|hole| !(4..=6).contains(&hole.len())) {
+            return Err(OmahaError::InvalidHole);
+        }
+        if board.len() > 5 {
+            return Err(OmahaError::InvalidBoard);
+        }
+        let dead: Vec<Card> = players.iter().flat_map(|hole| hole.iter()).chain(board).copied().collect();
+        if (1..dead.len()).any(|i| dead[..i].contains(&dead[i])) {
+            return Err(OmahaError::DuplicateCard);
+        }
+        Ok(Deck::create_ordered_deck().remaining(&dead))
+    }
+
+    /// function that averages the pot share of each player over the given runouts
+    ///
+    fn share<'a>(players: &[&[Card]], board: &[Card], runouts: impl Iterator<Item = &'a [Card]>) -> Vec<f64> {
+        let mut equity = vec![0.0; players.len()];
+        let mut count = 0;
+        for runout in runouts {
+            let full: [Card; 5] = board
+                .iter()
+                .chain(runout)
+                .copied()
+                .collect::<Vec<Card>>()
+                .try_into()
+                .unwrap();
+            let scores: Vec<Score> = players.iter().map(|hole| Self::score(hole, &full)).collect();
+            let best = scores.iter().min().unwrap();
+            let winners = scores.iter().filter(|s| *s == best).count() as f64;
+            for (e, s) in equity.iter_mut().zip(&scores) {
+                if s == best {
+                    *e += 1.0 / winners;
+                }
+            }
+            count += 1;
+        }
+        equity.iter().map(|e| e / count as f64).collect()
+    }
+}
+
+/// function that lists every combination of `k` of the given cards
+///
+fn combinations(cards: &[Card], k: usize) -> Vec<Vec<Card>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+    (0..cards.len())
+        .flat_map(|i| {
+            combinations(&cards[i + 1..], k - 1).into_iter().map(move |mut rest| {
+                rest.insert(0, cards[i]);
+                rest
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::{OmahaError, OmahaEvaluator};
+    use crate::card::{Card, Hand};
+    use crate::match_evaluator::Rank;
+    use crate::{cards, hand, newcard};
+
+    #[test]
+    fn omaha_needs_two_hole_cards_for_a_flush() {
+        // four hearts on board, but a single heart in the hand
+        let hole = cards!["Ah", "Ad", "Kc", "Qc"];
+        let board = cards!["2h", "7h", "9h", "Jh", "3s"];
+        let score = OmahaEvaluator::score(&hole, &board);
+        assert_eq!(score.rank, Rank::OnePair);
+        assert_eq!(score.kickers, [14, 14, 11, 9, 7]);
+
+        // with two hearts in the hand the flush is there
+        let hole = cards!["Ah", "Kh", "Kc", "Qc"];
+        assert_eq!(OmahaEvaluator::score(&hole, &board).rank, Rank::Flush);
+        assert_eq!(OmahaEvaluator::best_hand(&hole, &board), hand!["Ah", "Kh", "Jh", "9h", "7h"]);
+    }
+
+    #[test]
+    fn omaha_needs_three_board_cards() {
+        // four of a kind in the hand can only play as a pair
+        let hole = cards!["8h", "8d", "8c", "8s"];
+        let board = cards!["Ah", "Kd", "Qc", "5s", "2h"];
+        assert_eq!(OmahaEvaluator::score(&hole, &board).rank, Rank::OnePair);
+
+        // a straight on board doesn't play by itself
+        let hole = cards!["2c", "2d", "7c", "7d"];
+        let board = cards!["Ah", "Kd", "Qc", "Js", "10h"];
+        assert_eq!(OmahaEvaluator::score(&hole, &board).rank, Rank::OnePair);
+    }
+
+    #[test]
+    fn omaha_best_of_sixty() {
+        let hole = cards!["Qs", "Qd", "Jh", "10h"];
+        let board = cards!["Qh", "9h", "8h", "Kc", "Kd"];
+        assert_eq!(OmahaEvaluator::score(&hole, &board).rank, Rank::StraightFlush);
+
+        let hole = cards!["Qs", "Qd", "Jh", "10c"];
+        let score = OmahaEvaluator::score(&hole, &board);
+        assert_eq!(score.rank, Rank::FullHouse);
+        assert_eq!(score.kickers, [12, 12, 12, 13, 13]);
+    }
+
+    #[test]
+    fn omaha_five_and_six_cards() {
+        let board = cards!["Qh", "9h", "8h", "Kc", "2d"];
+        // the fifth card makes the straight
+        let hole = cards!["Ac", "Ad", "Js", "3c", "10h"];
+        assert_eq!(OmahaEvaluator::score(&hole[..4], &board).rank, Rank::OnePair);
+        assert_eq!(OmahaEvaluator::score(&hole, &board).rank, Rank::Straight);
+
+        // and the sixth the straight flush
+        let hole = cards!["Ac", "Ad", "Js", "3c", "10h", "Jh"];
+        assert_eq!(OmahaEvaluator::score(&hole, &board).rank, Rank::StraightFlush);
+        assert_eq!(OmahaEvaluator::best_hand(&hole, &board), hand!["Qh", "Jh", "10h", "9h", "8h"]);
+
+        // still no flush with a single heart, no matter how many hole cards
+        let hole = cards!["Ac", "Ad", "Ks", "Qs", "Jc", "Ah"];
+        assert_ne!(OmahaEvaluator::score(&hole, &board).rank, Rank::Flush);
+    }
+
+    #[test]
+    fn omaha_equity_on_the_river() {
+        let p1 = cards!["Ah", "Kh", "2c", "3d"];
+        let p2 = cards!["Qs", "Qd", "Jc", "10c"];
+        let board = cards!["Qh", "9h", "8h", "Kc", "2d"];
+        assert_eq!(OmahaEvaluator::equity(&[&p1, &p2], &board), Ok(vec![1.0, 0.0]));
+
+        // same hand values, split pot
+        let p1 = cards!["Ac", "Ad", "3c", "4d"];
+        let p2 = cards!["As", "Ah", "3s", "4h"];
+        let board = cards!["Kc", "Kd", "7s", "8h", "2c"];
+        assert_eq!(OmahaEvaluator::equity(&[&p1, &p2], &board), Ok(vec![0.5, 0.5]));
+    }
+
+    #[test]
+    fn omaha_equity_on_the_flop() {
+        let p1 = cards!["Ac", "Ad", "Ks", "Qs", "7c"];
+        let p2 = cards!["Jh", "10h", "9c", "8d", "2s"];
+        let board = cards!["As", "7h", "6h"];
+        let equity = OmahaEvaluator::equity(&[&p1, &p2], &board).unwrap();
+        assert!((equity[0] + equity[1] - 1.0).abs() < 1e-9);
+        // top set against a wrap with a flush draw is close to a coin flip
+        assert!(equity[0] > 0.4 && equity[1] > 0.4);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let sampled = OmahaEvaluator::sample_equity(&[&p1, &p2], &board, 2000, &mut rng).unwrap();
+        assert!((sampled[0] - equity[0]).abs() < 0.05);
+    }
+
+    #[test]
+    fn omaha_equity_invalid_input() {
+        let p1 = cards!["Ac", "Ad", "Ks", "Qs"];
+        let p2 = cards!["Jh", "10h", "9c", "8d"];
+        let flop = cards!["2s", "7h", "6h"];
+        assert_eq!(OmahaEvaluator::equity(&[&p1], &flop), Err(OmahaError::NotEnoughPlayers));
+        assert_eq!(OmahaEvaluator::equity(&[&p1, &p2[..3]], &flop), Err(OmahaError::InvalidHole));
+        assert_eq!(OmahaEvaluator::equity(&[&p1, &p2], &flop[..2]), Err(OmahaError::IncompleteBoard));
+        assert_eq!(OmahaEvaluator::equity(&[&p1, &p1], &flop), Err(OmahaError::DuplicateCard));
+        let board = [flop.as_slice(), &cards!["3c", "4c", "5c"]].concat();
+        assert_eq!(OmahaEvaluator::equity(&[&p1, &p2], &board), Err(OmahaError::InvalidBoard));
+
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(OmahaEvaluator::sample_equity(&[&p1, &p2], &[], 0, &mut rng), Err(OmahaError::NoTrials));
+    }
+
+    #[test]
+    fn omaha_low_needs_two_hole_cards() {
+        let board = cards!["2h", "5d", "7c", "Kc", "Qd"];
+        let low = OmahaEvaluator::low(&cards!["Ah", "3d", "Kd", "Ks"], &board).unwrap();
+        assert_eq!(low.kickers, [7, 5, 3, 2, 1]);
+
+        // a single low card in the hand, no low
+        assert_eq!(OmahaEvaluator::low(&cards!["Ah", "Jd", "Kd", "Ks"], &board), None);
+
+        // three low cards on board are needed
+        let board = cards!["2h", "5d", "9c", "Kc", "Qd"];
+        assert_eq!(OmahaEvaluator::low(&cards!["Ah", "3d", "4d", "6s"], &board), None);
+    }
+
+    #[test]
+    fn omaha_hi_lo_showdown() {
+        let board = cards!["2h", "5d", "7c", "Kc", "Qd"];
+        let p1 = cards!["Kd", "Ks", "9c", "9d"];
+        let p2 = cards!["Ah", "3d", "Jd", "10s"];
+        let p3 = cards!["As", "3c", "Jh", "10c"];
+
+        // set of kings scoops without a low
+        let showdown = OmahaEvaluator::hi_lo_showdown(&[&p1, &cards!["Jc", "Js", "9h", "9s"]], &board, 101).unwrap();
+        assert_eq!(showdown.payouts, vec![101, 0]);
+
+        // split, the odd chip goes high
+        let showdown = OmahaEvaluator::hi_lo_showdown(&[&p1, &p2], &board, 101).unwrap();
+        assert_eq!(showdown.high, vec![0]);
+        assert_eq!(showdown.low, vec![1]);
+        assert_eq!(showdown.payouts, vec![51, 50]);
+
+        // the low is quartered
+        let showdown = OmahaEvaluator::hi_lo_showdown(&[&p1, &p2, &p3], &board, 100).unwrap();
+        assert_eq!(showdown.low, vec![1, 2]);
+        assert_eq!(showdown.payouts, vec![50, 25, 25]);
+
+        assert_eq!(
+            OmahaEvaluator::hi_lo_showdown(&[&p1, &p1], &board, 100),
+            Err(OmahaError::DuplicateCard)
+        );
+    }
+}
@@ -0,0 +1,374 @@
+use std::cmp::{Ordering, Reverse};
+
+use crate::card::{Card, Hand};
+use crate::match_evaluator::{MatchHandEvaluator, Rank, Score};
+use crate::wild::{WildEvaluator, WildRules};
+
+/// The [`TwoCardScore`] struct. The value of the two-card (low) hand of Pai Gow poker: a pair, or two high cards.
+///
+/// The joker plays as an Ace. As for [`crate::match_evaluator::Score`], a better score compares as less.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TwoCardScore {
+    pub pair: bool,
+    pub kickers: [u8; 2],
+}
+
+impl PartialOrd for TwoCardScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TwoCardScore {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .pair
+            .cmp(&self.pair)
+            .then_with(|| other.kickers.cmp(&self.kickers))
+    }
+}
+
+impl TwoCardScore {
+    /// It evaluates a two-card hand.
+    ///
+    pub fn eval(cards: &[Card; 2]) -> TwoCardScore {
+        let mut kickers = cards.map(|c| value(&c));
+        kickers.sort_by_key(|v| Reverse(*v));
+        TwoCardScore {
+            pair: kickers[0] == kickers[1],
+            kickers,
+        }
+    }
+}
+
+/// The [`PaiGowOutcome`] enum. The result of a player's hand against the banker's.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PaiGowOutcome {
+    /// Both hands of the player beat the banker's ones.
+    Win,
+    /// One hand each.
+    Push,
+    /// Both hands of the banker win, copies (ties) included, or the player's hand is fouled.
+    Lose,
+}
+
+impl PaiGowOutcome {
+    /// It returns the net result of a `bet`, the winning bets paying even money minus a `commission` percentage (usually 5), rounded down.
+    ///
+    pub fn payout(&self, bet: u64, commission: u64) -> i64 {
+        match self {
+            PaiGowOutcome::Win => (bet - bet * commission / 100) as i64,
+            PaiGowOutcome::Push => 0,
+            PaiGowOutcome::Lose => -(bet as i64),
+        }
+    }
+}
+
+/// The [`PaiGowHand`] struct. Seven cards split into a five-card high hand and a two-card low hand.
+///
+/// Pai Gow poker is played with a 53-card deck, see [`crate::card::Deck::create_shuffled_deck_with_jokers`]: the joker is the bug,
+/// playing as an Ace or completing a straight, a flush or a straight flush.
+/// The high hand must beat the low hand, otherwise the hand is fouled.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct PaiGowHand {
+    pub high: [Card; 5],
+    pub low: [Card; 2],
+}
+
+impl PaiGowHand {
+    /// It lists the 21 ways of setting seven cards, fouled ones included.
+    ///
+    pub fn settings(cards: &[Card; 7]) -> Vec<PaiGowHand> {
+        let mut settings = vec![];
+        for i in 0..7 {
+            for j in i + 1..7 {
+                let high: Vec<Card> = (0..7).filter(|k| *k != i && *k != j).map(|k| cards[k]).collect();
+                settings.push(PaiGowHand {
+                    high: high.try_into().unwrap(),
+                    low: [cards[i], cards[j]],
+                });
+            }
+        }
+        settings
+    }
+
+    /// It evaluates the [Score] of the high hand, the joker playing as the bug.
+    ///
+    pub fn high_score(&self) -> Score {
+        let hand = Hand::new(self.high);
+        if self.high.iter().any(|c| c.is_joker()) {
+            WildEvaluator::score(&hand, &WildRules::bug())
+        } else {
+            MatchHandEvaluator::score(&mut hand.clone())
+        }
+    }
+
+    /// It evaluates the [TwoCardScore] of the low hand.
+    ///
+    pub fn low_score(&self) -> TwoCardScore {
+        TwoCardScore::eval(&self.low)
+    }
+
+    /// It tells if the hand is fouled, i.e. the low hand ranks higher than the high hand.
+    ///
+    pub fn is_fouled(&self) -> bool {
+        let (high, low) = (self.high_score(), self.low_score());
+        let high_pair = high.rank.position() <= Rank::OnePair.position();
+        match (low.pair, high.rank) {
+            (true, Rank::OnePair) => high.kickers[0] < low.kickers[0],
+            (true, _) => !high_pair,
+            (false, _) => !high_pair && high.kickers[..2] < low.kickers[..],
+        }
+    }
+
+    /// It settles the hand of a player against the banker's: the player must win both hands to win the bet, and copies go to the banker.
+    ///
+    pub fn against_banker(&self, banker: &PaiGowHand) -> PaiGowOutcome {
+        if self.is_fouled() {
+            return PaiGowOutcome::Lose;
+        }
+        if banker.is_fouled() {
+            return PaiGowOutcome::Win;
+        }
+        let high = self.high_score() < banker.high_score();
+        let low = self.low_score() < banker.low_score();
+        match (high, low) {
+            (true, true) => PaiGowOutcome::Win,
+            (false, false) => PaiGowOutcome::Lose,
+            _ => PaiGowOutcome::Push,
+        }
+    }
+}
+
+/// The [`HouseWay`] struct. The fixed strategy the house sets its hands with, tuned by a few knobs that vary between casinos.
+///
+/// - three pairs: the highest pair goes low;
+/// - two pairs: they are split, the lower pair going low, unless the highest pair is below `split_two_pairs_from` and an Ace can play low;
+/// - four of a kind: split into two pairs from `split_quads_from` up, unless there's another pair to play low;
+/// - full house: the highest pair goes low, the three of a kind high;
+/// - straights and flushes: played high with the best possible low hand;
+/// - three of a kind: kept high, but three Aces are split when `split_three_aces` is set;
+/// - one pair: played high, the two highest single cards low;
+/// - no pair: the highest card high, the next two low.
+///
+/// The joker counts as an Ace, unless it completes a straight or a flush.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct HouseWay {
+    pub split_two_pairs_from: u8,
+    pub split_quads_from: u8,
+    pub split_three_aces: bool,
+}
+
+impl Default for HouseWay {
+    /// Two pairs split from 7s, or without an Ace; four of a kind split from 7s; three Aces split.
+    ///
+    fn default() -> Self {
+        HouseWay {
+            split_two_pairs_from: 7,
+            split_quads_from: 7,
+            split_three_aces: true,
+        }
+    }
+}
+
+impl HouseWay {
+    /// It sets seven cards the house way.
+    ///
+    pub fn set(&self, cards: &[Card; 7]) -> PaiGowHand {
+        // cards grouped by value, the largest groups first, then the highest ones
+        let mut groups: Vec<Vec<Card>> = vec![];
+        let mut sorted = *cards;
+        sorted.sort_by_key(|c| Reverse(value(c)));
+        for card in sorted {
+            match groups.last_mut() {
+                Some(group) if value(&group[0]) == value(&card) => group.push(card),
+                _ => groups.push(vec![card]),
+            }
+        }
+        groups.sort_by_key(|g| Reverse((g.len(), value(&g[0]))));
+        let count = |n: usize| groups.iter().filter(|g| g.len() == n).count();
+        let singles: Vec<Card> = groups.iter().filter(|g| g.len() == 1).map(|g| g[0]).collect();
+        let (quads, trips, pairs) = (count(4), count(3), count(2));
+
+        let low: [Card; 2] = if groups[0].len() == 5 {
+            let rest = groups[1..].concat();
+            [rest[0], rest[1]]
+        } else if quads == 1 && groups.len() == 2 {
+            // four of a kind and three of a kind
+            [groups[1][0], groups[1][1]]
+        } else if quads == 1 && pairs == 1 {
+            [groups[1][0], groups[1][1]]
+        } else if quads == 1 && value(&groups[0][0]) >= self.split_quads_from {
+            [groups[0][0], groups[0][1]]
+        } else if quads == 1 {
+            [singles[0], singles[1]]
+        } else if trips == 2 {
+            [groups[0][0], groups[0][1]]
+        } else if trips == 1 && pairs >= 1 {
+            [groups[1][0], groups[1][1]]
+        } else if pairs == 3 {
+            [groups[0][0], groups[0][1]]
+        } else if pairs == 2 {
+            let ace = singles.first().filter(|c| value(c) == 14);
+            match ace {
+                Some(ace) if value(&groups[0][0]) < self.split_two_pairs_from => [*ace, singles[1]],
+                _ => [groups[1][0], groups[1][1]],
+            }
+        } else if let Some(hand) = Self::straight_or_flush(cards) {
+            return hand;
+        } else if trips == 1 && self.split_three_aces && value(&groups[0][0]) == 14 {
+            [groups[0][0], singles[0]]
+        } else if trips == 1 || pairs == 1 {
+            [singles[0], singles[1]]
+        } else {
+            [singles[1], singles[2]]
+        };
+
+        let high: Vec<Card> = sorted.iter().filter(|c| !low.contains(c)).copied().collect();
+        PaiGowHand {
+            high: high.try_into().unwrap(),
+            low,
+        }
+    }
+
+    /// function that plays a straight or a flush high, if any, with the best possible low hand
+    ///
+    fn straight_or_flush(cards: &[Card; 7]) -> Option<PaiGowHand> {
+        PaiGowHand::settings(cards)
+            .into_iter()
+            .filter(|hand| hand.high_score().rank.position() <= Rank::Straight.position() && !hand.is_fouled())
+            .min_by_key(|hand| (hand.low_score(), hand.high_score()))
+    }
+}
+
+/// function that returns the value of a card, the joker counting as an Ace
+///
+fn value(card: &Card) -> u8 {
+    if card.is_joker() {
+        14
+    } else {
+        card.val
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    use super::{HouseWay, PaiGowHand, PaiGowOutcome, TwoCardScore};
+    use crate::card::{Card, Deck, Hand, Suit};
+    use crate::match_evaluator::{MatchHandEvaluator, Rank};
+    use crate::{cards, hand, newcard};
+
+    fn with_joker(cards: [Card; 6]) -> [Card; 7] {
+        let mut hand = [Card::joker(Suit::Spades); 7];
+        hand[1..].copy_from_slice(&cards);
+        hand
+    }
+
+    fn pai_gow(high: [&str; 5], low: [&str; 2]) -> PaiGowHand {
+        PaiGowHand {
+            high: high.map(|c| newcard![c]),
+            low: low.map(|c| newcard![c]),
+        }
+    }
+
+    fn low(hand: &PaiGowHand) -> [u8; 2] {
+        hand.low_score().kickers
+    }
+
+    #[test]
+    fn pai_gow_two_card() {
+        let aces = TwoCardScore::eval(&[newcard!["Ah"], Card::joker(Suit::Spades)]);
+        assert!(aces.pair);
+        assert!(aces < TwoCardScore::eval(&[newcard!["Kh"], newcard!["Kd"]]));
+        assert!(TwoCardScore::eval(&[newcard!["2h"], newcard!["2d"]]) < TwoCardScore::eval(&[newcard!["Ah"], newcard!["Kd"]]));
+        assert!(TwoCardScore::eval(&[newcard!["Ah"], newcard!["Qd"]]) < TwoCardScore::eval(&[newcard!["Ah"], newcard!["Jd"]]));
+    }
+
+    #[test]
+    fn pai_gow_foul() {
+        assert_eq!(PaiGowHand::settings(&cards!["Ah", "Kd", "Qc", "9s", "7h", "5d", "3c"]).len(), 21);
+        assert!(!pai_gow(["Kh", "Kd", "9c", "7s", "3h"], ["Qh", "Qd"]).is_fouled());
+        assert!(!pai_gow(["Qs", "Qc", "9c", "7s", "3h"], ["Qh", "Qd"]).is_fouled());
+        assert!(pai_gow(["Jh", "Jd", "9c", "7s", "3h"], ["Qh", "Qd"]).is_fouled());
+        assert!(pai_gow(["Kh", "Jd", "9c", "7s", "3h"], ["Ah", "2d"]).is_fouled());
+        assert!(!pai_gow(["Ah", "Qd", "9c", "7s", "3h"], ["Ad", "Jd"]).is_fouled());
+        assert!(pai_gow(["Ah", "Jd", "9c", "7s", "3h"], ["Ad", "Qd"]).is_fouled());
+    }
+
+    #[test]
+    fn pai_gow_house_way() {
+        let house = HouseWay::default();
+        // no pair
+        assert_eq!(low(&house.set(&cards!["Ah", "Kd", "Qc", "9s", "7h", "5d", "3c"])), [13, 12]);
+        // one pair
+        assert_eq!(low(&house.set(&cards!["Ah", "Ad", "Qc", "9s", "7h", "5d", "3c"])), [12, 9]);
+        // two pairs, split
+        assert_eq!(low(&house.set(&cards!["Kh", "Kd", "4c", "4s", "Ah", "5d", "3c"])), [4, 4]);
+        // two low pairs with an Ace, kept together
+        assert_eq!(low(&house.set(&cards!["6h", "6d", "4c", "4s", "Ah", "9d", "3c"])), [14, 9]);
+        // three pairs
+        assert_eq!(low(&house.set(&cards!["6h", "6d", "4c", "4s", "Ah", "Ad", "3c"])), [14, 14]);
+        // full house
+        let hand = house.set(&cards!["6h", "6d", "6c", "4s", "4h", "Ad", "3c"]);
+        assert_eq!(low(&hand), [4, 4]);
+        assert_eq!(hand.high_score().rank, Rank::ThreeOfAKind);
+        // three aces, with the joker
+        assert_eq!(low(&house.set(&with_joker(cards!["Ah", "Ad", "Ks", "9h", "6d", "3c"]))), [14, 13]);
+        // four of a kind
+        assert_eq!(low(&house.set(&cards!["9h", "9d", "9c", "9s", "Ah", "Kd", "3c"])), [9, 9]);
+        assert_eq!(low(&house.set(&cards!["4h", "4d", "4c", "4s", "Ah", "Kd", "3c"])), [14, 13]);
+        // the joker completes the straight, played with the best low
+        let hand = house.set(&with_joker(cards!["9h", "8d", "7c", "5s", "Kd", "Qc"]));
+        assert_eq!(hand.high_score().rank, Rank::Straight);
+        assert_eq!(low(&hand), [13, 12]);
+        // the joker completing an Ace high flush is the highest missing heart, not a second Ace
+        let hand = house.set(&with_joker(cards!["Ah", "9h", "7h", "3h", "Kd", "Qc"]));
+        assert_eq!(hand.high_score(), MatchHandEvaluator::score(&mut hand!["Ad", "Kd", "9d", "7d", "3d"]));
+        assert_eq!(low(&hand), [13, 12]);
+    }
+
+    #[test]
+    fn pai_gow_house_way_never_fouls() {
+        let house = HouseWay::default();
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut cards = Deck::create_ordered_deck_with_jokers(1).remaining(&[]);
+        for _ in 0..200 {
+            let (dealt, _) = cards.partial_shuffle(&mut rng, 7);
+            let dealt = dealt.to_vec();
+            let hand = house.set(&dealt.clone().try_into().unwrap());
+            assert!(!hand.is_fouled(), "{:?}", dealt);
+            let mut set: Vec<Card> = hand.high.iter().chain(&hand.low).copied().collect();
+            set.sort_by_key(|c| (c.val, c.suit));
+            let mut dealt = dealt;
+            dealt.sort_by_key(|c| (c.val, c.suit));
+            assert_eq!(set, dealt);
+        }
+    }
+
+    #[test]
+    fn pai_gow_settlement() {
+        let banker = pai_gow(["Kh", "Kd", "9c", "7s", "3h"], ["Qh", "Jd"]);
+        let player = pai_gow(["Ah", "Ad", "9d", "7c", "3d"], ["Qd", "Js"]);
+        // the copy of the low hand goes to the banker
+        assert_eq!(player.against_banker(&banker), PaiGowOutcome::Push);
+        let player = pai_gow(["Ah", "Ad", "9d", "7c", "3d"], ["Kc", "Js"]);
+        assert_eq!(player.against_banker(&banker), PaiGowOutcome::Win);
+        let player = pai_gow(["Qs", "Qc", "9d", "7c", "3d"], ["Jc", "2s"]);
+        assert_eq!(player.against_banker(&banker), PaiGowOutcome::Lose);
+        // fouled
+        let player = pai_gow(["Ah", "9d", "8d", "7c", "3d"], ["Kc", "Ks"]);
+        assert_eq!(player.against_banker(&banker), PaiGowOutcome::Lose);
+
+        assert_eq!(PaiGowOutcome::Win.payout(100, 5), 95);
+        assert_eq!(PaiGowOutcome::Push.payout(100, 5), 0);
+        assert_eq!(PaiGowOutcome::Lose.payout(100, 5), -100);
+    }
+}
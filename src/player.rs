@@ -0,0 +1,242 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::card::Card;
+use crate::game::{Action, Game, LegalActions, Street};
+use crate::nash::StartingHand;
+use crate::strength::HandStrength;
+
+/// The [`Observation`] struct. What the player to act at a [`Game`] knows: the own cards, the board, the chips and the legal actions.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Observation {
+    pub seat: usize,
+    pub hole: Vec<Card>,
+    pub board: Vec<Card>,
+    pub street: Street,
+    pub pot: u64,
+    pub stack: u64,
+    /// The largest bet of the street, i.e. what a raise is on top of.
+    pub current_bet: u64,
+    pub big_blind: u64,
+    /// The other players still in the hand.
+    pub opponents: usize,
+    pub legal: LegalActions,
+}
+
+impl Observation {
+    /// It returns the observation of the player to act, if the hand isn't over.
+    ///
+    pub fn of(game: &Game) -> Option<Observation> {
+        let seat = game.to_act?;
+        Some(Observation {
+            seat,
+            hole: game.seats[seat].hole.clone(),
+            board: game.board.clone(),
+            street: game.street,
+            pot: game.pot(),
+            stack: game.seats[seat].stack,
+            current_bet: game.seats.iter().map(|s| s.bet).max().unwrap_or(0),
+            big_blind: game.blinds.big,
+            opponents: game.seats.iter().filter(|s| !s.folded).count() - 1,
+            legal: game.legal_actions()?,
+        })
+    }
+
+    /// It returns the [Action] to check if possible, or to call.
+    ///
+    pub fn check_or_call(&self) -> Action {
+        if self.legal.check {
+            Action::Check
+        } else {
+            Action::Call
+        }
+    }
+
+    /// It returns the [Action] to check if possible, or to fold.
+    ///
+    pub fn check_or_fold(&self) -> Action {
+        if self.legal.check {
+            Action::Check
+        } else {
+            Action::Fold
+        }
+    }
+
+    /// It returns the [Action] to bet or raise to the given amount, brought within the legal range, if betting or raising is allowed.
+    ///
+    pub fn raise_to(&self, to: u64) -> Option<Action> {
+        let (min, max) = self.legal.raise?;
+        let to = to.clamp(min, max);
+        Some(if self.current_bet == 0 {
+            Action::Bet(to)
+        } else {
+            Action::Raise(to)
+        })
+    }
+}
+
+/// The [`Player`] trait. An agent playing a [`Game`]: given what it observes, it returns its [`Action`].
+///
+pub trait Player {
+    /// It returns the action of the player, which should be legal for the observation.
+    ///
+    fn act(&mut self, observation: &Observation) -> Action;
+}
+
+/// The [`RandomPlayer`] struct. A player folding, checking or calling, or betting and raising any legal amount, at random.
+///
+/// It never folds when it can check.
+///
+pub struct RandomPlayer<R: Rng> {
+    rng: R,
+}
+
+impl<R: Rng> RandomPlayer<R> {
+    /// It creates the player, drawing its actions with the given random number generator.
+    ///
+    pub fn new(rng: R) -> RandomPlayer<R> {
+        RandomPlayer { rng }
+    }
+}
+
+impl<R: Rng> Player for RandomPlayer<R> {
+    fn act(&mut self, observation: &Observation) -> Action {
+        let mut actions = vec![observation.check_or_call()];
+        if !observation.legal.check {
+            actions.push(Action::Fold);
+        }
+        if let Some((min, max)) = observation.legal.raise {
+            let to = self.rng.gen_range(min..=max);
+            actions.extend(observation.raise_to(to));
+        }
+        *actions.choose(&mut self.rng).unwrap()
+    }
+}
+
+/// The [`CallingStation`] struct. A player that checks or calls whatever the cards, never betting nor folding.
+///
+pub struct CallingStation;
+
+impl Player for CallingStation {
+    fn act(&mut self, observation: &Observation) -> Action {
+        observation.check_or_call()
+    }
+}
+
+/// The [`TightAggressive`] struct. A player entering few pots, and playing them aggressively.
+///
+/// Preflop it raises to three times the bet with pairs of 8s or better and two cards from Jack up, calls with the other pairs,
+/// suited Aces, two cards from 10 up and suited connectors and one-gappers from 5-4 up, and folds the rest.
+/// After the flop it estimates the effective hand strength against the opponents left with [HandStrength::sample]:
+/// it bets two thirds of the pot or raises when it's at least `raise`, calls when it's at least `call` and beats the pot odds,
+/// else checks or folds.
+///
+/// It only plays Hold'em: dealt other than two hole cards (i.e. at an Omaha table), it always checks or folds.
+///
+pub struct TightAggressive<R: Rng> {
+    pub raise: f64,
+    pub call: f64,
+    pub samples: usize,
+    rng: R,
+}
+
+impl<R: Rng> TightAggressive<R> {
+    /// It creates the player raising from an effective hand strength of 0.8 and calling from 0.55, estimated with 100 samples.
+    ///
+    pub fn new(rng: R) -> TightAggressive<R> {
+        TightAggressive {
+            raise: 0.8,
+            call: 0.55,
+            samples: 100,
+            rng,
+        }
+    }
+
+    /// function that decides preflop by the starting hand
+    ///
+    fn preflop(&self, observation: &Observation, hand: &StartingHand) -> Action {
+        let pair = hand.high == hand.low;
+        if (pair && hand.high >= 8) || hand.low >= 11 {
+            let to = (observation.current_bet * 3).max(observation.big_blind * 3);
+            return observation.raise_to(to).unwrap_or_else(|| observation.check_or_call());
+        }
+        let connected = hand.suited && hand.high - hand.low <= 2 && hand.low >= 4;
+        if pair || (hand.suited && hand.high == 14) || hand.low >= 10 || connected {
+            observation.check_or_call()
+        } else {
+            observation.check_or_fold()
+        }
+    }
+}
+
+impl<R: Rng> Player for TightAggressive<R> {
+    fn act(&mut self, observation: &Observation) -> Action {
+        let Ok(hole) = <[Card; 2]>::try_from(observation.hole.as_slice()) else {
+            return observation.check_or_fold();
+        };
+        if observation.board.is_empty() {
+            return self.preflop(observation, &StartingHand::of(&hole));
+        }
+        let opponents = observation.opponents.max(1) as u32;
+        let Ok(strength) = HandStrength::sample(&hole, &observation.board, opponents, self.samples, &mut self.rng) else {
+            return observation.check_or_fold();
+        };
+        if strength.ehs >= self.raise {
+            let to = observation.current_bet + observation.pot * 2 / 3;
+            if let Some(action) = observation.raise_to(to) {
+                return action;
+            }
+        }
+        let call = observation.legal.call as f64;
+        let odds = call / (observation.pot as f64 + call);
+        if strength.ehs >= self.call && strength.ehs >= odds {
+            observation.check_or_call()
+        } else {
+            observation.check_or_fold()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::{CallingStation, Observation, Player, TightAggressive};
+    use crate::card::{Card, Deck};
+    use crate::game::{Action, Betting, Blinds, Game};
+    use crate::newcard;
+
+    #[test]
+    fn player_observation() {
+        let blinds = Blinds {
+            small: 5,
+            big: 10,
+            ante: 0,
+        };
+        // seat 1 (the small blind) gets Ah Ad, seat 2 (the big blind) Kh Kd, the button 7c 2d
+        let cards: Vec<Card> = ["Ah", "Kh", "7c", "Ad", "Kd", "2d"].iter().map(|c| newcard![*c]).collect();
+        let mut game = Game::new(&[1000, 1000, 1000], 0, blinds, Betting::NoLimit, Deck::new(cards)).unwrap();
+        let observation = Observation::of(&game).unwrap();
+        assert_eq!(observation.seat, 0);
+        assert_eq!(observation.pot, 15);
+        assert_eq!(observation.current_bet, 10);
+        assert_eq!(observation.opponents, 2);
+        assert_eq!(observation.raise_to(5), Some(Action::Raise(20)));
+        assert_eq!(observation.raise_to(5000), Some(Action::Raise(1000)));
+        assert_eq!(CallingStation.act(&observation), Action::Call);
+
+        // the tight aggressive player folds 7-2 and raises aces to three times the bet
+        let mut tag = TightAggressive::new(StdRng::seed_from_u64(42));
+        assert_eq!(tag.act(&observation), Action::Fold);
+        game.act(Action::Fold).unwrap();
+        assert_eq!(tag.act(&Observation::of(&game).unwrap()), Action::Raise(30));
+
+        // at an Omaha table it doesn't play, whatever the cards
+        let game = Game::omaha(&[1000, 1000, 1000], 0, blinds, Betting::PotLimit, Deck::create_shuffled_deck()).unwrap();
+        let observation = Observation::of(&game).unwrap();
+        assert_eq!(observation.hole.len(), 4);
+        assert_eq!(tag.act(&observation), Action::Fold);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::showdown::Showdown;
+
+/// The [`Pot`] struct. The main pot or a side pot: the chips in it and the seats of the players eligible to win it.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pot {
+    pub amount: u64,
+    pub eligible: Vec<usize>,
+}
+
+/// It builds the main pot and the side pots out of the chips contributed by each player during the hand, the main pot first.
+///
+/// Every level of contribution of the players who haven't folded closes a pot, which only the players who contributed at least
+/// that much are eligible for. The chips of the players who folded stay in the pots they reached.
+/// An uncalled bet makes a last pot with a single eligible player, i.e. it goes back to the bettor.
+///
+pub fn pots(contributions: &[u64], folded: &[bool]) -> Vec<Pot> {
+    let mut levels: Vec<u64> = contributions
+        .iter()
+        .zip(folded)
+        .filter(|(_, folded)| !**folded)
+        .map(|(c, _)| *c)
+        .collect();
+    levels.sort();
+    levels.dedup();
+
+    let mut pots = vec![];
+    let mut previous = 0;
+    for (l, level) in levels.iter().enumerate() {
+        // the last pot collects whatever is left, the chips of the players who folded included
+        let top = if l == levels.len() - 1 { u64::MAX } else { *level };
+        let amount = contributions.iter().map(|c| c.min(&top) - c.min(&previous)).sum();
+        let eligible = (0..contributions.len())
+            .filter(|i| !folded[*i] && contributions[*i] >= *level)
+            .collect();
+        if amount > 0 {
+            pots.push(Pot { amount, eligible });
+        }
+        previous = *level;
+    }
+    pots
+}
+
+/// The [`PotResult`] struct. How a single pot is awarded, the players being identified by their seat.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct PotResult {
+    pub pot: Pot,
+    pub high: Vec<usize>,
+    pub low: Vec<usize>,
+    /// The chips won by each seat out of this pot.
+    pub payouts: Vec<u64>,
+}
+
+/// The [`Ledger`] struct. The full account of a hand: how every pot is awarded, the chips each player collects, and the net result of each player.
+///
+/// The players are identified by their seat, the button being given to assign odd chips to the first winners left of it.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Ledger {
+    pub pots: Vec<PotResult>,
+    pub payouts: Vec<u64>,
+    pub net: Vec<i64>,
+}
+
+impl Ledger {
+    /// It awards every pot to the best high hands among its eligible players, splitting it in case of a tie.
+    ///
+    /// As for [Showdown::high], a hand is better when it compares as less (i.e. a [crate::match_evaluator::Score]).
+    /// A pot with a single eligible player goes to that player whatever the hand, so hands can be `None` when the hand is won without a showdown.
+    ///
+    pub fn high<H: Ord>(contributions: &[u64], folded: &[bool], button: usize, highs: &[Option<H>]) -> Ledger {
+        Self::settle(contributions, folded, button, |amount, eligible| {
+            let highs = Self::rotate(button, eligible, highs);
+            Showdown::high(amount, &highs)
+        })
+    }
+
+    /// It splits every pot of a Hi-Lo game between the best high hands and the best qualifying low hands among its eligible players,
+    /// a low being `None` when it doesn't qualify.
+    ///
+    /// Each pot is split on its own: a player can scoop a side pot while the main pot is split. See [Showdown::hi_lo].
+    ///
+    pub fn hi_lo<H: Ord, L: Ord>(
+        contributions: &[u64],
+        folded: &[bool],
+        button: usize,
+        highs: &[Option<H>],
+        lows: &[Option<L>],
+    ) -> Ledger {
+        Self::settle(contributions, folded, button, |amount, eligible| {
+            let highs = Self::rotate(button, eligible, highs);
+            let lows = Self::rotate(button, eligible, lows);
+            Showdown::hi_lo(amount, &highs, &lows)
+        })
+    }
+
+    /// function that builds the pots and awards each of them with the given showdown, played by the eligible players in seat order left of the button
+    ///
+    fn settle(contributions: &[u64], folded: &[bool], button: usize, showdown: impl Fn(u64, &[bool]) -> Showdown) -> Ledger {
+        let n = contributions.len();
+        let order: Vec<usize> = (1..=n).map(|k| (button + k) % n).collect();
+        let mut payouts = vec![0; n];
+
+        let pots = pots(contributions, folded)
+            .into_iter()
+            .map(|pot| {
+                let mut result = PotResult {
+                    high: vec![],
+                    low: vec![],
+                    payouts: vec![0; n],
+                    pot,
+                };
+                if let [winner] = result.pot.eligible[..] {
+                    result.high = vec![winner];
+                    result.payouts[winner] = result.pot.amount;
+                } else {
+                    let eligible: Vec<bool> = order.iter().map(|i| result.pot.eligible.contains(i)).collect();
+                    let showdown = showdown(result.pot.amount, &eligible);
+                    result.high = showdown.high.iter().map(|r| order[*r]).collect();
+                    result.low = showdown.low.iter().map(|r| order[*r]).collect();
+                    for (i, payout) in order.iter().zip(showdown.payouts) {
+                        result.payouts[*i] = payout;
+                    }
+                }
+                for (total, payout) in payouts.iter_mut().zip(&result.payouts) {
+                    *total += payout;
+                }
+                result
+            })
+            .collect();
+
+        let net = payouts.iter().zip(contributions).map(|(p, c)| *p as i64 - *c as i64).collect();
+        Ledger { pots, payouts, net }
+    }
+
+    /// function that lists the hands in seat order left of the button, the ones of the players not eligible for the pot being `None`
+    ///
+    fn rotate<'a, T>(button: usize, eligible: &[bool], hands: &'a [Option<T>]) -> Vec<Option<&'a T>> {
+        let n = hands.len();
+        (1..=n)
+            .map(|k| (button + k) % n)
+            .zip(eligible)
+            .map(|(i, eligible)| hands[i].as_ref().filter(|_| *eligible))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{pots, Ledger, Pot};
+
+    #[test]
+    fn pot_side_pots() {
+        let pots = pots(&[100, 300, 300, 50], &[false, false, false, true]);
+        assert_eq!(
+            pots,
+            vec![
+                Pot {
+                    amount: 350,
+                    eligible: vec![0, 1, 2],
+                },
+                Pot {
+                    amount: 400,
+                    eligible: vec![1, 2],
+                },
+            ]
+        );
+
+        // the uncalled bet goes back
+        assert_eq!(super::pots(&[100, 500], &[false, false])[1], Pot { amount: 400, eligible: vec![1] });
+        // a folded player's chips above the last level stay in the last pot
+        assert_eq!(super::pots(&[200, 100, 100], &[true, false, false]), vec![Pot { amount: 400, eligible: vec![1, 2] }]);
+    }
+
+    #[test]
+    fn pot_ledger_high() {
+        // seat 0 is all-in with the best hand, seats 1 and 2 tie for the side pot
+        let ledger = Ledger::high(&[100, 301, 301, 50], &[false, false, false, true], 3, &[Some(1), Some(2), Some(2), None]);
+        assert_eq!(ledger.pots[0].high, vec![0]);
+        assert_eq!(ledger.pots[1].high, vec![1, 2]);
+        assert_eq!(ledger.payouts, vec![350, 201, 201, 0]);
+        assert_eq!(ledger.net, vec![250, -100, -100, -50]);
+        assert_eq!(ledger.net.iter().sum::<i64>(), 0);
+
+        // the odd chip goes to the first winner left of the button
+        let ledger = Ledger::high(&[101, 101, 101], &[false, false, true], 1, &[Some(1), Some(1), None]);
+        assert_eq!(ledger.payouts, vec![152, 151, 0]);
+        let ledger = Ledger::high(&[101, 101, 101], &[false, false, true], 0, &[Some(1), Some(1), None]);
+        assert_eq!(ledger.payouts, vec![151, 152, 0]);
+
+        // won without a showdown
+        let ledger = Ledger::high::<u8>(&[10, 40, 5], &[true, false, true], 0, &[None, None, None]);
+        assert_eq!(ledger.payouts, vec![0, 55, 0]);
+    }
+
+    #[test]
+    fn pot_ledger_hi_lo() {
+        // seat 0 is all-in with the best low, seat 1 has the best high, seat 2 the second best high and no low
+        let contributions = [100, 300, 300];
+        let highs = [Some(3), Some(1), Some(2)];
+        let lows = [Some(1), Some(2), None];
+        let ledger = Ledger::hi_lo(&contributions, &[false; 3], 2, &highs, &lows);
+        // the main pot of 300 is split, the side pot of 400 is split between seat 1 (high) and its low
+        assert_eq!(ledger.pots[0].high, vec![1]);
+        assert_eq!(ledger.pots[0].low, vec![0]);
+        assert_eq!(ledger.pots[1].low, vec![1]);
+        assert_eq!(ledger.payouts, vec![150, 550, 0]);
+
+        // no qualifying low in the side pot: the high scoops it
+        let lows = [Some(1), None, None];
+        let ledger = Ledger::hi_lo(&contributions, &[false; 3], 2, &highs, &lows);
+        assert_eq!(ledger.payouts, vec![150, 550, 0]);
+        assert_eq!(ledger.pots[1].low, Vec::<usize>::new());
+    }
+}
@@ -0,0 +1,115 @@
+/// The [`Showdown`] struct. It's the outcome of a showdown for a single pot: who wins the high half, who wins the low half, and how many chips each player is awarded.
+///
+/// The players are identified by their index, in seat order starting from the first seat to the left of the button, so that odd chips
+/// go to the first winners to the left of the button. The hands of the players who are not eligible for the pot (i.e. folded) are `None`.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Showdown {
+    pub high: Vec<usize>,
+    pub low: Vec<usize>,
+    pub payouts: Vec<u64>,
+}
+
+impl Showdown {
+    /// It awards the whole `pot` to the best high hands, splitting it in case of a tie.
+    ///
+    /// A hand is better when it compares as less, like a [crate::match_evaluator::Score] does.
+    ///
+    pub fn high<H: Ord>(pot: u64, highs: &[Option<H>]) -> Showdown {
+        let high = winners(highs);
+        let mut payouts = vec![0; highs.len()];
+        divide(pot, &high, &mut payouts);
+        Showdown {
+            high,
+            low: vec![],
+            payouts,
+        }
+    }
+
+    /// It splits the `pot` of a Hi-Lo game between the best high hands and the best qualifying low hands, a low being `None` when it doesn't qualify.
+    ///
+    /// Without any qualifying low, the high hands scoop the whole pot. Otherwise each half is split among its winners (i.e. quartered),
+    /// and the odd chip of an uneven pot goes to the high half.
+    ///
+    pub fn hi_lo<H: Ord, L: Ord>(pot: u64, highs: &[Option<H>], lows: &[Option<L>]) -> Showdown {
+        let low = winners(lows);
+        if low.is_empty() {
+            return Self::high(pot, highs);
+        }
+        let high = winners(highs);
+        let mut payouts = vec![0; highs.len()];
+        divide(pot - pot / 2, &high, &mut payouts);
+        divide(pot / 2, &low, &mut payouts);
+        Showdown { high, low, payouts }
+    }
+}
+
+/// It returns the indexes of the best hands, i.e. the ones comparing as less, skipping the `None` ones.
+///
+pub fn winners<T: Ord>(hands: &[Option<T>]) -> Vec<usize> {
+    let Some(best) = hands.iter().flatten().min() else {
+        return vec![];
+    };
+    hands
+        .iter()
+        .enumerate()
+        .filter(|(_, hand)| hand.as_ref() == Some(best))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// function that divides an amount of chips among the winners, the odd chips going one each to the first winners
+///
+fn divide(amount: u64, winners: &[usize], payouts: &mut [u64]) {
+    if winners.is_empty() {
+        return;
+    }
+    let n = winners.len() as u64;
+    for (i, w) in winners.iter().enumerate() {
+        payouts[*w] += amount / n + u64::from((i as u64) < amount % n);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{winners, Showdown};
+
+    #[test]
+    fn showdown_winners() {
+        assert_eq!(winners(&[Some(3), Some(1), None, Some(1)]), vec![1, 3]);
+        assert_eq!(winners::<u8>(&[None, None]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn showdown_high_only() {
+        let showdown = Showdown::high(100, &[Some(2), Some(1), Some(3)]);
+        assert_eq!(showdown.high, vec![1]);
+        assert_eq!(showdown.payouts, vec![0, 100, 0]);
+
+        // the odd chip goes to the first winner left of the button
+        let showdown = Showdown::high(101, &[Some(2), Some(1), Some(1)]);
+        assert_eq!(showdown.payouts, vec![0, 51, 50]);
+    }
+
+    #[test]
+    fn showdown_hi_lo() {
+        // no qualifying low, the high scoops
+        let showdown = Showdown::hi_lo::<u8, u8>(100, &[Some(2), Some(1)], &[None, None]);
+        assert_eq!(showdown.low, Vec::<usize>::new());
+        assert_eq!(showdown.payouts, vec![0, 100]);
+
+        // split, the odd chip goes high
+        let showdown = Showdown::hi_lo(101, &[Some(2), Some(1)], &[Some(1), None]);
+        assert_eq!(showdown.payouts, vec![50, 51]);
+
+        // scoop of both halves
+        let showdown = Showdown::hi_lo(100, &[Some(1), Some(2)], &[Some(1), Some(2)]);
+        assert_eq!(showdown.payouts, vec![100, 0]);
+
+        // quartered: one player wins the high and ties the low
+        let showdown = Showdown::hi_lo(100, &[Some(1), Some(2), Some(3)], &[Some(1), Some(1), None]);
+        assert_eq!(showdown.high, vec![0]);
+        assert_eq!(showdown.low, vec![0, 1]);
+        assert_eq!(showdown.payouts, vec![75, 25, 0]);
+    }
+}
@@ -0,0 +1,295 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use thiserror::Error;
+
+use crate::card::{Card, Deck};
+use crate::match_evaluator::{MatchHandEvaluator, Score};
+
+/// The [`HandStrength`] struct. It collects the classic effective hand strength metrics of a Texas Hold'em hand on a given board:
+///
+/// - `hs`: the probability that the hand is currently ahead of the opponents' random holdings (ties count half),
+/// - `ppot`: the positive potential, the probability of ending ahead when currently behind or tied,
+/// - `npot`: the negative potential, the probability of ending behind when currently ahead or tied,
+/// - `ehs`: the effective hand strength, `hs * (1 - npot) + (1 - hs) * ppot`.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct HandStrength {
+    pub hs: f64,
+    pub ppot: f64,
+    pub npot: f64,
+    pub ehs: f64,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum StrengthError {
+    #[error("The board must have 3, 4 or 5 cards")]
+    InvalidBoard,
+
+    #[error("There must be at least one opponent")]
+    NoOpponents,
+
+    #[error("The same card is dealt twice")]
+    DuplicateCard,
+}
+
+/// The outcome of a showdown against one opponent, used to index the hand potential table.
+///
+#[derive(Debug, Clone, Copy)]
+enum Outcome {
+    Ahead = 0,
+    Tied = 1,
+    Behind = 2,
+}
+
+impl Outcome {
+    fn of(ours: &Score, theirs: &Score) -> Outcome {
+        // a stronger score compares as less
+        match ours.cmp(theirs) {
+            std::cmp::Ordering::Less => Outcome::Ahead,
+            std::cmp::Ordering::Equal => Outcome::Tied,
+            std::cmp::Ordering::Greater => Outcome::Behind,
+        }
+    }
+}
+
+impl HandStrength {
+    /// It computes the [`HandStrength`] of the `hole` cards on a flop, turn or river `board`, against a number of `opponents` holding random cards.
+    ///
+    /// Every opponent holding and every runout up to the river is enumerated and evaluated with [`MatchHandEvaluator::best_score`].
+    /// Against more than one opponent the hand strength is `hs` raised to the number of opponents, while the potentials are computed heads-up.
+    ///
+    pub fn evaluate(hole: &[Card; 2], board: &[Card], opponents: u32) -> Result<HandStrength, StrengthError> {
+        let dead = Self::validate(hole, board, opponents)?;
+        let remaining = Deck::create_ordered_deck().remaining(&dead);
+        let runouts = Self::runouts(&remaining, 5 - board.len());
+
+        // our final score does not depend on the opponent, so it's computed once per runout
+        let ours: Vec<Score> = runouts
+            .iter()
+            .map(|runout| Self::showdown(hole, board, runout))
+            .collect();
+        let now = MatchHandEvaluator::best_score(&dead);
+
+        let mut current = [0u64; 3];
+        let mut hp = [[0u64; 3]; 3];
+        let mut hp_total = [0u64; 3];
+
+        for i in 0..remaining.len() {
+            for j in i + 1..remaining.len() {
+                let opp = [remaining[i], remaining[j]];
+                let index = Outcome::of(&now, &Self::showdown(&opp, board, &[])) as usize;
+                current[index] += 1;
+
+                for (runout, our) in runouts.iter().zip(&ours) {
+                    if runout.contains(&opp[0]) || runout.contains(&opp[1]) {
+                        continue;
+                    }
+                    let theirs = Self::showdown(&opp, board, runout);
+                    hp[index][Outcome::of(our, &theirs) as usize] += 1;
+                    hp_total[index] += 1;
+                }
+            }
+        }
+
+        Ok(Self::from_counts(current, hp, hp_total, opponents))
+    }
+
+    /// It estimates the [`HandStrength`] as [`HandStrength::evaluate`] does, sampling `samples` random opponent holdings and runouts:
+    /// fast enough to be computed at every decision, i.e. by a bot on the flop.
+    ///
+    pub fn sample<R: Rng>(
+        hole: &[Card; 2],
+        board: &[Card],
+        opponents: u32,
+        samples: usize,
+        rng: &mut R,
+    ) -> Result<HandStrength, StrengthError> {
+        let dead = Self::validate(hole, board, opponents)?;
+        let mut remaining = Deck::create_ordered_deck().remaining(&dead);
+        let now = MatchHandEvaluator::best_score(&dead);
+
+        let mut current = [0u64; 3];
+        let mut hp = [[0u64; 3]; 3];
+        let mut hp_total = [0u64; 3];
+        for _ in 0..samples {
+            let (cards, _) = remaining.partial_shuffle(rng, 7 - board.len());
+            let (opp, runout) = ([cards[0], cards[1]], &cards[2..]);
+            let index = Outcome::of(&now, &Self::showdown(&opp, board, &[])) as usize;
+            current[index] += 1;
+            let (our, theirs) = (Self::showdown(hole, board, runout), Self::showdown(&opp, board, runout));
+            hp[index][Outcome::of(&our, &theirs) as usize] += 1;
+            hp_total[index] += 1;
+        }
+        Ok(Self::from_counts(current, hp, hp_total, opponents))
+    }
+
+    /// function that checks the input, returning the known cards
+    ///
+    fn validate(hole: &[Card; 2], board: &[Card], opponents: u32) -> Result<Vec<Card>, StrengthError> {
+        if !(3..=5).contains(&board.len()) {
+            return Err(StrengthError::InvalidBoard);
+        }
+        if opponents == 0 {
+            return Err(StrengthError::NoOpponents);
+        }
+        let dead: Vec<Card> = hole.iter().chain(board).copied().collect();
+        if (1..dead.len()).any(|i| dead[..i].contains(&dead[i])) {
+            return Err(StrengthError::DuplicateCard);
+        }
+        Ok(dead)
+    }
+
+    /// function that computes the metrics out of the counts of the current outcomes and of the outcomes at the river
+    ///
+    fn from_counts(current: [u64; 3], hp: [[u64; 3]; 3], hp_total: [u64; 3], opponents: u32) -> HandStrength {
+        let [ahead, tied, behind] = current.map(|n| n as f64);
+        let hs = ((ahead + tied / 2.0) / (ahead + tied + behind)).powi(opponents as i32);
+
+        let hp = hp.map(|row| row.map(|n| n as f64));
+        let [total_ahead, total_tied, total_behind] = hp_total.map(|n| n as f64);
+        let (a, t, b) = (Outcome::Ahead as usize, Outcome::Tied as usize, Outcome::Behind as usize);
+
+        let ppot = Self::ratio(
+            hp[b][a] + hp[b][t] / 2.0 + hp[t][a] / 2.0,
+            total_behind + total_tied / 2.0,
+        );
+        let npot = Self::ratio(
+            hp[a][b] + hp[t][b] / 2.0 + hp[a][t] / 2.0,
+            total_ahead + total_tied / 2.0,
+        );
+
+        HandStrength {
+            hs,
+            ppot,
+            npot,
+            ehs: hs * (1.0 - npot) + (1.0 - hs) * ppot,
+        }
+    }
+
+    /// function that evaluates the best score of 2 hole cards, the board and the runout
+    ///
+    fn showdown(hole: &[Card; 2], board: &[Card], runout: &[Card]) -> Score {
+        let cards: Vec<Card> = hole.iter().chain(board).chain(runout).copied().collect();
+        MatchHandEvaluator::best_score(&cards)
+    }
+
+    /// function that lists every possible runout of `n` cards (0, 1 or 2) out of the remaining cards
+    ///
+    fn runouts(remaining: &[Card], n: usize) -> Vec<Vec<Card>> {
+        match n {
+            0 => vec![vec![]],
+            1 => remaining.iter().map(|c| vec![*c]).collect(),
+            _ => (0..remaining.len())
+                .flat_map(|i| (i + 1..remaining.len()).map(move |j| vec![remaining[i], remaining[j]]))
+                .collect(),
+        }
+    }
+
+    /// function that divides two counts, returning 0 when there's nothing to divide
+    ///
+    fn ratio(num: f64, den: f64) -> f64 {
+        if den == 0.0 {
+            0.0
+        } else {
+            num / den
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::{HandStrength, StrengthError};
+    use crate::card::Card;
+    use crate::newcard;
+
+    #[test]
+    fn strength_of_the_nuts_on_the_river() {
+        let hole = [newcard!["Ah"], newcard!["Kh"]];
+        let board = [newcard!["Qh"], newcard!["Jh"], newcard!["10h"], newcard!["2c"], newcard!["3d"]];
+        let strength = HandStrength::evaluate(&hole, &board, 1).unwrap();
+        assert_eq!(strength.hs, 1.0);
+        assert_eq!(strength.ppot, 0.0);
+        assert_eq!(strength.npot, 0.0);
+        assert_eq!(strength.ehs, 1.0);
+    }
+
+    #[test]
+    fn strength_when_the_board_plays() {
+        let hole = [newcard!["2c"], newcard!["3d"]];
+        let board = [newcard!["As"], newcard!["Ks"], newcard!["Qs"], newcard!["Js"], newcard!["10s"]];
+        let strength = HandStrength::evaluate(&hole, &board, 1).unwrap();
+        assert_eq!(strength.hs, 0.5);
+        assert_eq!(strength.ehs, 0.5);
+    }
+
+    #[test]
+    fn strength_of_high_cards() {
+        // no straight nor flush is on: the Ace high beats the unpaired hands but Ace-Queen, the Queen high only the weaker ones
+        let board = [newcard!["Kc"], newcard!["9s"], newcard!["7h"], newcard!["4d"], newcard!["2c"]];
+        let ace = HandStrength::evaluate(&[newcard!["Ah"], newcard!["Jd"]], &board, 1).unwrap();
+        let queen = HandStrength::evaluate(&[newcard!["Qh"], newcard!["Jh"]], &board, 1).unwrap();
+        assert!(ace.hs > queen.hs);
+    }
+
+    #[test]
+    fn strength_against_many_opponents() {
+        let hole = [newcard!["Ac"], newcard!["7d"]];
+        let board = [newcard!["As"], newcard!["9h"], newcard!["4c"], newcard!["Jd"], newcard!["2s"]];
+        let one = HandStrength::evaluate(&hole, &board, 1).unwrap();
+        let three = HandStrength::evaluate(&hole, &board, 3).unwrap();
+        assert!(one.hs > 0.0 && one.hs < 1.0);
+        assert!((three.hs - one.hs.powi(3)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn strength_potentials_on_the_turn() {
+        // an open ended straight flush draw is behind most of the time, but has plenty of outs
+        let draw = HandStrength::evaluate(
+            &[newcard!["9h"], newcard!["8h"]],
+            &[newcard!["7h"], newcard!["6h"], newcard!["Ks"], newcard!["2d"]],
+            1,
+        )
+        .unwrap();
+        assert!(draw.ppot > 0.3);
+        assert!(draw.ehs > draw.hs);
+
+        // top set is far ahead, and can only be outdrawn
+        let set = HandStrength::evaluate(
+            &[newcard!["Kd"], newcard!["Kc"]],
+            &[newcard!["7h"], newcard!["6h"], newcard!["Ks"], newcard!["2d"]],
+            1,
+        )
+        .unwrap();
+        assert!(set.hs > 0.9);
+        assert!(set.npot > 0.0);
+        assert!(set.ppot < set.npot);
+        assert!(set.ehs < set.hs);
+        let expected = set.hs * (1.0 - set.npot) + (1.0 - set.hs) * set.ppot;
+        assert!((set.ehs - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn strength_sampled() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let hole = [newcard!["9h"], newcard!["8h"]];
+        let board = [newcard!["7h"], newcard!["6h"], newcard!["Ks"], newcard!["2d"]];
+        let exact = HandStrength::evaluate(&hole, &board, 1).unwrap();
+        let sampled = HandStrength::sample(&hole, &board, 1, 5_000, &mut rng).unwrap();
+        assert!((exact.hs - sampled.hs).abs() < 0.03);
+        assert!((exact.ehs - sampled.ehs).abs() < 0.03);
+        assert_eq!(HandStrength::sample(&hole, &board, 0, 10, &mut rng), Err(StrengthError::NoOpponents));
+    }
+
+    #[test]
+    fn strength_invalid_input() {
+        let hole = [newcard!["Ah"], newcard!["Kh"]];
+        let flop = [newcard!["Qh"], newcard!["Jh"], newcard!["10h"]];
+        assert_eq!(HandStrength::evaluate(&hole, &flop[..2], 1), Err(StrengthError::InvalidBoard));
+        assert_eq!(HandStrength::evaluate(&hole, &flop, 0), Err(StrengthError::NoOpponents));
+        let board = [newcard!["Ah"], newcard!["Jh"], newcard!["10h"]];
+        assert_eq!(HandStrength::evaluate(&hole, &board, 1), Err(StrengthError::DuplicateCard));
+    }
+}
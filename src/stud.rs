@@ -0,0 +1,282 @@
+use thiserror::Error;
+
+use crate::card::{Card, Deck};
+use crate::lowball::{LowRank, LowballEvaluator};
+use crate::match_evaluator::{MatchHandEvaluator, Score};
+use crate::showdown::Showdown;
+
+/// The [`StudVariant`] enum. It represents the games played with the Seven-Card Stud structure.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StudVariant {
+    /// Seven-Card Stud, the best high hand wins.
+    Stud,
+    /// Seven-Card Stud Hi-Lo, the pot is split between the best high hand and the best eight-or-better Ace-to-Five low.
+    StudHiLo,
+    /// Razz, the best Ace-to-Five low hand wins.
+    Razz,
+}
+
+/// The [`Street`] enum. It represents the dealing rounds of a Stud hand.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Street {
+    /// Two down cards and one up card.
+    Third,
+    /// One up card.
+    Fourth,
+    /// One up card.
+    Fifth,
+    /// One up card.
+    Sixth,
+    /// One down card (the river).
+    Seventh,
+}
+
+impl Street {
+    /// It returns the [Street] dealt after this one, if any.
+    ///
+    pub const fn next(&self) -> Option<Street> {
+        match self {
+            Street::Third => Some(Street::Fourth),
+            Street::Fourth => Some(Street::Fifth),
+            Street::Fifth => Some(Street::Sixth),
+            Street::Sixth => Some(Street::Seventh),
+            Street::Seventh => None,
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum StudError {
+    #[error("A Stud game needs from 2 to 7 players")]
+    InvalidPlayers,
+
+    #[error("All the streets have already been dealt")]
+    HandOver,
+
+    #[error("The hand needs to be dealt up to the seventh street")]
+    NotDealt,
+
+    #[error("The deck has run out of cards")]
+    EmptyDeck,
+}
+
+/// The [`StudHand`] struct. The cards of a Stud player, split between the down cards (known to the player only) and the up cards (shown to everybody).
+///
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct StudHand {
+    pub down: Vec<Card>,
+    pub up: Vec<Card>,
+}
+
+impl StudHand {
+    /// It lists all the cards of the hand, down cards first.
+    ///
+    pub fn cards(&self) -> Vec<Card> {
+        self.down.iter().chain(&self.up).copied().collect()
+    }
+}
+
+/// The [`StudTable`] struct. It deals a Stud hand street by street to players sitting in seat order, and settles the showdown.
+///
+#[derive(Debug, Clone)]
+pub struct StudTable {
+    pub variant: StudVariant,
+    pub hands: Vec<StudHand>,
+    pub street: Option<Street>,
+    pub folded: Vec<bool>,
+    deck: Deck,
+}
+
+impl StudTable {
+    /// It sits the given number of players at the table, ready to be dealt from the `deck`.
+    ///
+    pub fn new(variant: StudVariant, players: usize, deck: Deck) -> Result<StudTable, StudError> {
+        if !(2..=7).contains(&players) {
+            return Err(StudError::InvalidPlayers);
+        }
+        Ok(StudTable {
+            variant,
+            hands: vec![StudHand::default(); players],
+            street: None,
+            folded: vec![false; players],
+            deck,
+        })
+    }
+
+    /// It deals the next [Street] to the players still in the hand, one card at a time and in seat order.
+    ///
+    pub fn deal_street(&mut self) -> Result<Street, StudError> {
+        let street = match self.street {
+            None => Street::Third,
+            Some(street) => street.next().ok_or(StudError::HandOver)?,
+        };
+        // whether the card is dealt face up, for each round of dealing
+        let rounds: &[bool] = match street {
+            Street::Third => &[false, false, true],
+            Street::Seventh => &[false],
+            _ => &[true],
+        };
+        for up in rounds {
+            for (hand, folded) in self.hands.iter_mut().zip(&self.folded) {
+                if *folded {
+                    continue;
+                }
+                let card = self.deck.deal().ok_or(StudError::EmptyDeck)?;
+                if *up {
+                    hand.up.push(card);
+                } else {
+                    hand.down.push(card);
+                }
+            }
+        }
+        self.street = Some(street);
+        Ok(street)
+    }
+
+    /// The player at the given seat folds, giving up the hand.
+    ///
+    pub fn fold(&mut self, player: usize) {
+        self.folded[player] = true;
+    }
+
+    /// It returns the seat of the player forced to bring in, once the third street is dealt.
+    ///
+    /// In Stud and Stud Hi-Lo it's the lowest up card, in Razz the highest one (the Ace being low), ties broken by [crate::card::Suit] order:
+    /// the lowest suit brings in for Stud, the highest for Razz.
+    ///
+    pub fn bring_in(&self) -> Option<usize> {
+        let door = |i: usize| self.hands[i].up.first().copied();
+        let seats = (0..self.hands.len()).filter(|i| door(*i).is_some());
+        match self.variant {
+            StudVariant::Stud | StudVariant::StudHiLo => seats.min_by_key(|i| door(*i).map(|c| (c.val, c.suit))),
+            StudVariant::Razz => seats.max_by_key(|i| {
+                door(*i).map(|c| (if c.val == 14 { 1 } else { c.val }, c.suit))
+            }),
+        }
+    }
+
+    /// It settles the `pot` among the players who haven't folded, once the seventh street is dealt, according to the [StudVariant].
+    ///
+    pub fn showdown(&self, pot: u64) -> Result<Showdown, StudError> {
+        if self.street != Some(Street::Seventh) {
+            return Err(StudError::NotDealt);
+        }
+        let live = self.hands.iter().zip(&self.folded).map(|(hand, folded)| (!folded).then(|| hand.cards()));
+        Ok(match self.variant {
+            StudVariant::Stud => {
+                let highs: Vec<Option<Score>> = live.map(|c| c.map(|c| MatchHandEvaluator::best_score(&c))).collect();
+                Showdown::high(pot, &highs)
+            }
+            StudVariant::Razz => {
+                let lows: Vec<Option<LowRank>> = live.map(|c| c.map(|c| LowballEvaluator::best_ace_to_five(&c))).collect();
+                Showdown::high(pot, &lows)
+            }
+            StudVariant::StudHiLo => {
+                let cards: Vec<Option<Vec<Card>>> = live.collect();
+                let highs: Vec<Option<Score>> = cards
+                    .iter()
+                    .map(|c| c.as_ref().map(|c| MatchHandEvaluator::best_score(c)))
+                    .collect();
+                let lows: Vec<Option<LowRank>> = cards
+                    .iter()
+                    .map(|c| c.as_ref().and_then(|c| LowballEvaluator::eight_or_better(c)))
+                    .collect();
+                Showdown::hi_lo(pot, &highs, &lows)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Street, StudError, StudTable, StudVariant};
+    use crate::card::{Card, Deck};
+    use crate::newcard;
+
+    /// a deck dealing the given hands of 7 cards, in the order down, down, up, up, up, up, down
+    fn deck(hands: &[[&str; 7]]) -> Deck {
+        let mut cards = vec![];
+        for i in 0..7 {
+            cards.extend(hands.iter().map(|h| newcard![h[i]]));
+        }
+        Deck::new(cards)
+    }
+
+    #[test]
+    fn stud_streets() {
+        let mut table = StudTable::new(StudVariant::Stud, 3, Deck::create_shuffled_deck()).unwrap();
+        assert_eq!(table.deal_street(), Ok(Street::Third));
+        assert!(table.hands.iter().all(|h| h.down.len() == 2 && h.up.len() == 1));
+        table.fold(1);
+        for street in [Street::Fourth, Street::Fifth, Street::Sixth, Street::Seventh] {
+            assert_eq!(table.deal_street(), Ok(street));
+        }
+        assert_eq!(table.deal_street(), Err(StudError::HandOver));
+        assert_eq!(table.hands[0].down.len(), 3);
+        assert_eq!(table.hands[0].up.len(), 4);
+        assert_eq!(table.hands[1].cards().len(), 3);
+
+        assert_eq!(StudTable::new(StudVariant::Razz, 8, Deck::create_shuffled_deck()).err(), Some(StudError::InvalidPlayers));
+    }
+
+    #[test]
+    fn stud_bring_in() {
+        let hands = [
+            ["Ah", "Kh", "2s", "3c", "4c", "5c", "6c"],
+            ["Ad", "Kd", "2c", "3d", "4d", "5d", "6d"],
+            ["As", "Ks", "Kc", "3h", "4h", "5h", "6h"],
+        ];
+        let mut stud = StudTable::new(StudVariant::Stud, 3, deck(&hands)).unwrap();
+        assert_eq!(stud.bring_in(), None);
+        stud.deal_street().unwrap();
+        // the deuce of clubs is lower than the deuce of spades
+        assert_eq!(stud.bring_in(), Some(1));
+
+        let mut razz = StudTable::new(StudVariant::Razz, 3, deck(&hands)).unwrap();
+        razz.deal_street().unwrap();
+        assert_eq!(razz.bring_in(), Some(2));
+
+        let hands = [
+            ["2h", "3h", "Kd", "3c", "4c", "5c", "6c"],
+            ["2d", "3d", "Ks", "3s", "4d", "5d", "6d"],
+            ["2s", "3s", "Ah", "3h", "4h", "5h", "6h"],
+        ];
+        // the Ace is low in Razz, and the king of spades beats the king of diamonds
+        let mut razz = StudTable::new(StudVariant::Razz, 3, deck(&hands)).unwrap();
+        razz.deal_street().unwrap();
+        assert_eq!(razz.bring_in(), Some(1));
+    }
+
+    #[test]
+    fn stud_showdown() {
+        let hands = [
+            ["Ah", "Ad", "Kc", "Kd", "9s", "8s", "2c"],
+            ["2h", "3h", "4s", "5d", "7c", "Qs", "Jd"],
+        ];
+        let mut table = StudTable::new(StudVariant::Stud, 2, deck(&hands)).unwrap();
+        table.deal_street().unwrap();
+        assert_eq!(table.showdown(100), Err(StudError::NotDealt));
+        while table.deal_street().is_ok() {}
+        assert_eq!(table.showdown(100).unwrap().payouts, vec![100, 0]);
+
+        let mut razz = StudTable::new(StudVariant::Razz, 2, deck(&hands)).unwrap();
+        while razz.deal_street().is_ok() {}
+        assert_eq!(razz.showdown(100).unwrap().payouts, vec![0, 100]);
+
+        let mut hi_lo = StudTable::new(StudVariant::StudHiLo, 2, deck(&hands)).unwrap();
+        while hi_lo.deal_street().is_ok() {}
+        let showdown = hi_lo.showdown(101).unwrap();
+        assert_eq!(showdown.high, vec![0]);
+        assert_eq!(showdown.low, vec![1]);
+        assert_eq!(showdown.payouts, vec![51, 50]);
+
+        // a folded player can't win
+        let mut table = StudTable::new(StudVariant::Stud, 2, deck(&hands)).unwrap();
+        table.deal_street().unwrap();
+        table.fold(0);
+        while table.deal_street().is_ok() {}
+        assert_eq!(table.showdown(100).unwrap().payouts, vec![0, 100]);
+    }
+}
@@ -0,0 +1,287 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::card::{Card, Deck};
+use crate::isomorphism::Isomorphism;
+
+/// The [`ThreeCardRank`] enum represents the hand ranks of Three Card Poker from highest to lowest.
+///
+/// With three cards a Straight is harder to make than a Flush, so it ranks higher, and Three of a Kind beats a Straight.
+///
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, strum_macros::Display)]
+pub enum ThreeCardRank {
+    /// Three consecutive cards of the same suit.
+    StraightFlush,
+    /// Three cards of the same value.
+    ThreeOfAKind,
+    /// Three consecutive cards, the Ace playing high (A K Q) or low (3 2 A).
+    Straight,
+    /// Three cards of the same suit.
+    Flush,
+    /// Two cards of the same value.
+    Pair,
+    /// None of the above.
+    HighCard,
+}
+
+/// The [`ThreeCardScore`] struct. A [`ThreeCardRank`] plus the card values, to compare hands of the same rank.
+///
+/// The kickers are grouped by multiplicity, then sorted by value: i.e. 5 5 K gives [5, 5, 13], while the lowest straight 3 2 A gives [3, 2, 1].
+/// As for [`crate::match_evaluator::Score`], a better score compares as less.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ThreeCardScore {
+    pub rank: ThreeCardRank,
+    pub kickers: [u8; 3],
+}
+
+impl PartialOrd for ThreeCardScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ThreeCardScore {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank
+            .cmp(&other.rank)
+            .then_with(|| other.kickers.cmp(&self.kickers))
+    }
+}
+
+impl ThreeCardScore {
+    /// It tells if the dealer's hand qualifies, i.e. it's Queen high or better.
+    ///
+    pub fn qualifies(&self) -> bool {
+        self.rank < ThreeCardRank::HighCard || self.kickers[0] >= 12
+    }
+
+    /// It tells if the player should play the hand, according to the optimal strategy: Q 6 4 or better.
+    ///
+    pub fn is_playable(&self) -> bool {
+        self.rank < ThreeCardRank::HighCard || self.kickers >= [12, 6, 4]
+    }
+}
+
+/// The [`ThreeCardEvaluator`] struct. It evaluates hands of three cards.
+///
+pub struct ThreeCardEvaluator;
+
+impl ThreeCardEvaluator {
+    /// It evaluates the [ThreeCardScore] of three cards.
+    ///
+    pub fn eval(cards: &[Card; 3]) -> ThreeCardScore {
+        let mut vals = cards.map(|c| c.val);
+        vals.sort_by(|a, b| b.cmp(a));
+        let [v1, v2, v3] = vals;
+        let flush = cards.iter().all(|c| c.suit == cards[0].suit);
+        let straight = (v1 == v2 + 1 && v2 == v3 + 1) || vals == [14, 3, 2];
+
+        let (rank, kickers) = match vals {
+            _ if v1 == v3 => (ThreeCardRank::ThreeOfAKind, vals),
+            [14, 3, 2] if flush => (ThreeCardRank::StraightFlush, [3, 2, 1]),
+            [14, 3, 2] => (ThreeCardRank::Straight, [3, 2, 1]),
+            _ if straight && flush => (ThreeCardRank::StraightFlush, vals),
+            _ if straight => (ThreeCardRank::Straight, vals),
+            _ if flush => (ThreeCardRank::Flush, vals),
+            _ if v1 == v2 => (ThreeCardRank::Pair, vals),
+            _ if v2 == v3 => (ThreeCardRank::Pair, [v2, v3, v1]),
+            _ => (ThreeCardRank::HighCard, vals),
+        };
+        ThreeCardScore { rank, kickers }
+    }
+}
+
+/// The [`ThreeCardPaytable`] struct. The pays of Three Card Poker, in units per unit bet, for each [`ThreeCardRank`].
+///
+/// The Ante bonus is paid to a player who plays, whatever the dealer's hand. The Pair Plus is a side bet on the player's hand alone.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct ThreeCardPaytable {
+    pub ante_bonus: Vec<(ThreeCardRank, u32)>,
+    pub pair_plus: Vec<(ThreeCardRank, u32)>,
+}
+
+impl ThreeCardPaytable {
+    /// The most common paytable: Ante bonus 5/4/1 and Pair Plus 40/30/6/3/1.
+    ///
+    pub fn standard() -> ThreeCardPaytable {
+        ThreeCardPaytable {
+            ante_bonus: vec![
+                (ThreeCardRank::StraightFlush, 5),
+                (ThreeCardRank::ThreeOfAKind, 4),
+                (ThreeCardRank::Straight, 1),
+            ],
+            pair_plus: vec![
+                (ThreeCardRank::StraightFlush, 40),
+                (ThreeCardRank::ThreeOfAKind, 30),
+                (ThreeCardRank::Straight, 6),
+                (ThreeCardRank::Flush, 3),
+                (ThreeCardRank::Pair, 1),
+            ],
+        }
+    }
+
+    /// It settles the Ante and Play bets (one unit each), returning the player's net win in units.
+    ///
+    /// A player who folds loses the Ante. Otherwise the Ante bonus is paid, then if the dealer doesn't qualify the Ante wins and the Play pushes,
+    /// else both bets win, push or lose against the dealer's hand.
+    ///
+    pub fn ante_play(&self, player: &ThreeCardScore, dealer: &ThreeCardScore, play: bool) -> i64 {
+        if !play {
+            return -1;
+        }
+        let bonus = i64::from(Self::pays(&self.ante_bonus, player.rank));
+        bonus
+            + match player.cmp(dealer) {
+                _ if !dealer.qualifies() => 1,
+                Ordering::Less => 2,
+                Ordering::Equal => 0,
+                Ordering::Greater => -2,
+            }
+    }
+
+    /// It settles a Pair Plus bet of one unit, returning the player's net win in units.
+    ///
+    pub fn pair_plus(&self, player: &ThreeCardScore) -> i64 {
+        match Self::pays(&self.pair_plus, player.rank) {
+            0 => -1,
+            pay => i64::from(pay),
+        }
+    }
+
+    /// It computes the house edge of the Ante and Play, as the player's expected loss per unit of Ante, playing the optimal Q 6 4 strategy.
+    ///
+    /// Every player hand is enumerated against every dealer hand out of the remaining 49 cards, the player hands being grouped by suit isomorphism.
+    ///
+    pub fn ante_play_edge(&self) -> f64 {
+        let cards = Deck::create_ordered_deck().remaining(&[]);
+        let hands = Self::hands(&cards);
+
+        // the player hands isomorphic to each other share the same expected value
+        let mut classes: HashMap<Vec<Card>, (usize, u64)> = HashMap::new();
+        for (h, (hand, _)) in hands.iter().enumerate() {
+            let (canonical, _) = Isomorphism::canonical(&hand.map(|i| cards[i]), &[]);
+            classes.entry(canonical).or_insert((h, 0)).1 += 1;
+        }
+
+        let mut total = 0.0;
+        for (h, count) in classes.values() {
+            let (hand, player) = hands[*h];
+            let play = player.is_playable();
+            let (mut win, mut deals) = (0i64, 0i64);
+            for (other, dealer) in &hands {
+                if other.iter().any(|i| hand.contains(i)) {
+                    continue;
+                }
+                win += self.ante_play(&player, dealer, play);
+                deals += 1;
+            }
+            total += *count as f64 * win as f64 / deals as f64;
+        }
+        -total / hands.len() as f64
+    }
+
+    /// It computes the house edge of the Pair Plus, as the player's expected loss per unit bet, enumerating every hand.
+    ///
+    pub fn pair_plus_edge(&self) -> f64 {
+        let cards = Deck::create_ordered_deck().remaining(&[]);
+        let hands = Self::hands(&cards);
+        let total: i64 = hands.iter().map(|(_, score)| self.pair_plus(score)).sum();
+        -total as f64 / hands.len() as f64
+    }
+
+    /// function that returns the pay of a rank in a list of pays, if any
+    ///
+    fn pays(lines: &[(ThreeCardRank, u32)], rank: ThreeCardRank) -> u32 {
+        lines.iter().find(|(r, _)| *r == rank).map_or(0, |(_, pay)| *pay)
+    }
+
+    /// function that scores the 22100 hands of three cards, along with the indexes of their cards
+    ///
+    fn hands(cards: &[Card]) -> Vec<([usize; 3], ThreeCardScore)> {
+        let n = cards.len();
+        let mut hands = Vec::new();
+        for i in 0..n {
+            for j in i + 1..n {
+                for k in j + 1..n {
+                    hands.push(([i, j, k], ThreeCardEvaluator::eval(&[cards[i], cards[j], cards[k]])));
+                }
+            }
+        }
+        hands
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ThreeCardEvaluator, ThreeCardPaytable, ThreeCardRank, ThreeCardScore};
+    use crate::card::Card;
+    use crate::newcard;
+
+    fn eval(cards: [&str; 3]) -> ThreeCardScore {
+        ThreeCardEvaluator::eval(&cards.map(|c| newcard![c]))
+    }
+
+    #[test]
+    fn three_card_ranks() {
+        assert_eq!(eval(["Qh", "Kh", "Ah"]).rank, ThreeCardRank::StraightFlush);
+        assert_eq!(eval(["7h", "7d", "7c"]).rank, ThreeCardRank::ThreeOfAKind);
+        assert_eq!(eval(["2h", "3d", "Ac"]).kickers, [3, 2, 1]);
+        assert_eq!(eval(["2h", "3d", "Ac"]).rank, ThreeCardRank::Straight);
+        assert_eq!(eval(["2h", "9h", "Ah"]).rank, ThreeCardRank::Flush);
+        assert_eq!(eval(["Kc", "5h", "5d"]).kickers, [5, 5, 13]);
+        assert_eq!(eval(["Kc", "Jh", "5d"]).rank, ThreeCardRank::HighCard);
+        assert_eq!(eval(["Kc", "Ah", "2d"]).rank, ThreeCardRank::HighCard);
+    }
+
+    #[test]
+    fn three_card_comparison() {
+        // a straight beats a flush
+        assert!(eval(["2h", "3d", "Ac"]) < eval(["Ah", "Kh", "9h"]));
+        assert!(eval(["7h", "7d", "7c"]) < eval(["Qh", "Kd", "Ac"]));
+        assert!(eval(["Qh", "Kd", "Ac"]) < eval(["2h", "3d", "4c"]));
+        assert!(eval(["2h", "3d", "4c"]) < eval(["2d", "9d", "Ad"]));
+        assert!(eval(["5h", "5d", "Kc"]) < eval(["5c", "5s", "Qc"]));
+        assert_eq!(eval(["5h", "5d", "Kc"]), eval(["5c", "5s", "Kh"]));
+    }
+
+    #[test]
+    fn three_card_qualify_and_play() {
+        assert!(eval(["Qh", "3d", "2c"]).qualifies());
+        assert!(!eval(["Jh", "10d", "8c"]).qualifies());
+        assert!(eval(["2h", "2d", "3c"]).qualifies());
+
+        assert!(eval(["Qh", "6d", "4c"]).is_playable());
+        assert!(!eval(["Qh", "6d", "3c"]).is_playable());
+        assert!(eval(["Kh", "3d", "2c"]).is_playable());
+    }
+
+    #[test]
+    fn three_card_settlement() {
+        let table = ThreeCardPaytable::standard();
+        let trips = eval(["7h", "7d", "7c"]);
+        let pair = eval(["2h", "2d", "Kc"]);
+        let jack = eval(["Jh", "10d", "8c"]);
+        let queen = eval(["Qh", "10d", "8c"]);
+
+        assert_eq!(table.ante_play(&pair, &queen, false), -1);
+        assert_eq!(table.ante_play(&pair, &jack, true), 1);
+        assert_eq!(table.ante_play(&pair, &queen, true), 2);
+        assert_eq!(table.ante_play(&queen, &pair, true), -2);
+        assert_eq!(table.ante_play(&queen, &queen, true), 0);
+        // the ante bonus is paid even when the dealer has a better hand
+        assert_eq!(table.ante_play(&trips, &eval(["Ah", "Kh", "Qh"]), true), 2);
+
+        assert_eq!(table.pair_plus(&trips), 30);
+        assert_eq!(table.pair_plus(&pair), 1);
+        assert_eq!(table.pair_plus(&jack), -1);
+    }
+
+    #[test]
+    fn three_card_house_edge() {
+        let table = ThreeCardPaytable::standard();
+        assert!((table.pair_plus_edge() - 0.0728).abs() < 1e-4);
+        assert!((table.ante_play_edge() - 0.0337).abs() < 1e-4);
+    }
+}
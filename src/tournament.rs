@@ -0,0 +1,435 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use thiserror::Error;
+
+use crate::card::Deck;
+use crate::game::{Action, Betting, Blinds, Game, GameError};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum TournamentError {
+    #[error("A tournament needs at least 2 players")]
+    InvalidPlayers,
+
+    #[error("A table seats from 2 to 10 players")]
+    InvalidTableSize,
+
+    #[error("The blind schedule needs at least one level")]
+    EmptySchedule,
+
+    #[error("The tournament is over")]
+    TournamentOver,
+
+    #[error("The hand failed: {0}")]
+    Game(#[from] GameError),
+}
+
+/// The [`Level`] struct. A level of the blind schedule: the blinds and the ante, and how many minutes it lasts.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Level {
+    pub blinds: Blinds,
+    pub minutes: u32,
+}
+
+/// The [`BlindSchedule`] struct. The levels of a tournament, the last one lasting until the end.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct BlindSchedule {
+    pub levels: Vec<Level>,
+}
+
+impl BlindSchedule {
+    /// It builds a schedule of `count` levels of the given minutes, starting from the given blinds and raising them by half each level,
+    /// rounded to the small blind, with an ante of an eighth of the big blind from the fourth level on.
+    ///
+    pub fn standard(small: u64, minutes: u32, count: usize) -> BlindSchedule {
+        let levels = (0..count)
+            .scan(small as f64, |current, level| {
+                let small = (*current / small as f64).round() as u64 * small;
+                *current *= 1.5;
+                Some(Level {
+                    blinds: Blinds {
+                        small,
+                        big: 2 * small,
+                        ante: if level >= 3 { small / 4 } else { 0 },
+                    },
+                    minutes,
+                })
+            })
+            .collect();
+        BlindSchedule { levels }
+    }
+
+    /// It returns the index of the level played after the given minutes of play, the last one lasting forever.
+    ///
+    pub fn level_at(&self, elapsed: u32) -> usize {
+        let mut end = 0;
+        for (l, level) in self.levels.iter().enumerate() {
+            end += level.minutes;
+            if elapsed < end {
+                return l;
+            }
+        }
+        self.levels.len() - 1
+    }
+
+    /// It returns the [Blinds] played after the given minutes of play.
+    ///
+    pub fn blinds_at(&self, elapsed: u32) -> Blinds {
+        self.levels[self.level_at(elapsed)].blinds
+    }
+}
+
+/// The [`Payouts`] struct. The share of the prize pool paid to each finishing position, the winner first.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Payouts {
+    pub shares: Vec<f64>,
+}
+
+impl Payouts {
+    /// The whole prize pool to the winner.
+    ///
+    pub fn winner_take_all() -> Payouts {
+        Payouts { shares: vec![1.0] }
+    }
+
+    /// The common 50/30/20 of a single table tournament.
+    ///
+    pub fn top_three() -> Payouts {
+        Payouts {
+            shares: vec![0.5, 0.3, 0.2],
+        }
+    }
+
+    /// It returns the prizes of the paid positions for the given pool, the chips lost rounding down going to the winner.
+    ///
+    pub fn prizes(&self, pool: u64) -> Vec<u64> {
+        let mut prizes: Vec<u64> = self.shares.iter().map(|share| (pool as f64 * share) as u64).collect();
+        let paid: u64 = prizes.iter().sum();
+        if let Some(first) = prizes.first_mut() {
+            *first += pool.saturating_sub(paid);
+        }
+        prizes
+    }
+}
+
+/// The [`Move`] struct. A player moved to another table, by table breaking or balancing.
+///
+/// The tables are identified by their index at the time of the move: a broken table by its index before it's removed,
+/// the destination by its index once it's removed.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Move {
+    pub player: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// The [`Tournament`] struct. A multi-table tournament: the players sitting at the tables with their stacks, the blinds clock,
+/// and the finishing positions of the players knocked out.
+///
+/// Players are identified by their index. Each table lists the players sitting at it in seat order, with the index of the button.
+/// After every hand the busted players are knocked out, the tables are broken as soon as the remaining players fit in fewer of them,
+/// and the others balanced so that no table has two players more than another.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Tournament {
+    pub schedule: BlindSchedule,
+    pub betting: Betting,
+    pub table_size: usize,
+    pub stacks: Vec<u64>,
+    pub tables: Vec<Vec<usize>>,
+    pub buttons: Vec<usize>,
+    /// The finishing position of each player, from 1, once knocked out (or as the winner).
+    pub positions: Vec<Option<usize>>,
+    /// The minutes of play elapsed.
+    pub elapsed: u32,
+}
+
+impl Tournament {
+    /// It seats the players, each with the starting stack, at as few tables of `table_size` seats as needed, balanced.
+    ///
+    pub fn new(
+        players: usize,
+        stack: u64,
+        table_size: usize,
+        schedule: BlindSchedule,
+        betting: Betting,
+    ) -> Result<Tournament, TournamentError> {
+        if players < 2 {
+            return Err(TournamentError::InvalidPlayers);
+        }
+        if !(2..=10).contains(&table_size) {
+            return Err(TournamentError::InvalidTableSize);
+        }
+        if schedule.levels.is_empty() {
+            return Err(TournamentError::EmptySchedule);
+        }
+        let count = players.div_ceil(table_size);
+        let mut tables = vec![vec![]; count];
+        for player in 0..players {
+            tables[player % count].push(player);
+        }
+        Ok(Tournament {
+            schedule,
+            betting,
+            table_size,
+            stacks: vec![stack; players],
+            tables,
+            buttons: vec![0; count],
+            positions: vec![None; players],
+            elapsed: 0,
+        })
+    }
+
+    /// It returns the [Blinds] of the current level.
+    ///
+    pub fn blinds(&self) -> Blinds {
+        self.schedule.blinds_at(self.elapsed)
+    }
+
+    /// It returns the players still in the tournament.
+    ///
+    pub fn remaining(&self) -> Vec<usize> {
+        (0..self.stacks.len()).filter(|p| self.positions[*p].is_none()).collect()
+    }
+
+    /// It tells if the tournament is over, i.e. a single player is left.
+    ///
+    pub fn is_over(&self) -> bool {
+        self.remaining().len() <= 1
+    }
+
+    /// It advances the clock by the given minutes.
+    ///
+    pub fn tick(&mut self, minutes: u32) {
+        self.elapsed += minutes;
+    }
+
+    /// It records the stacks of the players of a table after a hand, in seat order: the busted players are knocked out,
+    /// the button moves on, and the tables are broken and balanced. It returns the players moved.
+    ///
+    /// Players busted in the same hand finish in order of the stack they started the hand with, the larger one first.
+    ///
+    pub fn record(&mut self, table: usize, stacks: &[u64]) -> Vec<Move> {
+        let players = self.tables[table].clone();
+        let mut busted: Vec<usize> = vec![];
+        for (player, stack) in players.iter().zip(stacks) {
+            if *stack == 0 {
+                busted.push(*player);
+            }
+        }
+        // the smaller starting stacks finish first, i.e. in the worse positions
+        busted.sort_by_key(|p| self.stacks[*p]);
+        let mut left = self.remaining().len();
+        for player in busted {
+            self.positions[player] = Some(left);
+            left -= 1;
+        }
+        for (player, stack) in players.iter().zip(stacks) {
+            self.stacks[*player] = *stack;
+        }
+
+        // the button moves to the next seat still playing
+        let button = self.buttons[table];
+        let next = (1..=players.len())
+            .map(|k| (button + k) % players.len())
+            .find(|s| self.positions[players[*s]].is_none());
+        self.tables[table].retain(|p| self.positions[*p].is_none());
+        if let Some(next) = next {
+            let button = players[next];
+            self.buttons[table] = self.tables[table].iter().position(|p| *p == button).unwrap_or(0);
+        }
+        if left == 1 {
+            let winner = self.remaining()[0];
+            self.positions[winner] = Some(1);
+            return vec![];
+        }
+        self.balance()
+    }
+
+    /// It breaks the tables no longer needed, the shortest one first, and moves players from the longest tables to the shortest ones
+    /// until they differ by one player at most. It returns the players moved.
+    ///
+    pub fn balance(&mut self) -> Vec<Move> {
+        let mut moves = vec![];
+        let players: usize = self.tables.iter().map(|t| t.len()).sum();
+        while self.tables.len() > 1 && players <= (self.tables.len() - 1) * self.table_size {
+            let (broken, _) = self.tables.iter().enumerate().min_by_key(|(_, t)| t.len()).unwrap();
+            let seated = self.tables.remove(broken);
+            self.buttons.remove(broken);
+            for player in seated {
+                let (to, _) = self.tables.iter().enumerate().min_by_key(|(_, t)| t.len()).unwrap();
+                self.tables[to].push(player);
+                moves.push(Move { player, from: broken, to });
+            }
+        }
+        loop {
+            let (from, longest) = self.tables.iter().enumerate().max_by_key(|(_, t)| t.len()).unwrap();
+            let (to, shortest) = self.tables.iter().enumerate().min_by_key(|(_, t)| t.len()).unwrap();
+            if longest.len() <= shortest.len() + 1 {
+                break;
+            }
+            // the player who would be the next big blind moves
+            let seat = (self.buttons[from] + 2) % longest.len();
+            let player = self.tables[from].remove(seat);
+            if seat < self.buttons[from] {
+                self.buttons[from] -= 1;
+            }
+            self.buttons[from] %= self.tables[from].len();
+            self.tables[to].push(player);
+            moves.push(Move { player, from, to });
+        }
+        moves
+    }
+
+    /// It plays a hand at the given table with the current blinds, dealing from a deck shuffled with the `rng`,
+    /// every player acting according to the strategy, then records the result.
+    ///
+    pub fn play_hand<R: Rng>(
+        &mut self,
+        table: usize,
+        rng: &mut R,
+        strategy: &mut impl FnMut(&Game) -> Action,
+    ) -> Result<Vec<Move>, TournamentError> {
+        if self.is_over() {
+            return Err(TournamentError::TournamentOver);
+        }
+        let stacks: Vec<u64> = self.tables[table].iter().map(|p| self.stacks[*p]).collect();
+        let mut cards = Deck::create_ordered_deck().remaining(&[]);
+        cards.shuffle(rng);
+        let mut game = Game::new(&stacks, self.buttons[table], self.blinds(), self.betting, Deck::new(cards))?;
+        while !game.is_over() {
+            game.act(strategy(&game))?;
+        }
+        let stacks: Vec<u64> = game.seats.iter().map(|s| s.stack).collect();
+        Ok(self.record(table, &stacks))
+    }
+
+    /// It plays the tournament to the end, a hand at every table in turn, the clock advancing by `hand_minutes` after each round.
+    /// It returns the players in finishing order, the winner first.
+    ///
+    pub fn run<R: Rng>(
+        &mut self,
+        rng: &mut R,
+        hand_minutes: u32,
+        mut strategy: impl FnMut(&Game) -> Action,
+    ) -> Result<Vec<usize>, TournamentError> {
+        while !self.is_over() {
+            let mut table = 0;
+            while table < self.tables.len() && !self.is_over() {
+                // a table left with a single player waits for the balancing
+                if self.tables[table].len() > 1 {
+                    self.play_hand(table, rng, &mut strategy)?;
+                }
+                table += 1;
+            }
+            self.tick(hand_minutes);
+        }
+        Ok(self.standings())
+    }
+
+    /// It returns the players knocked out so far in finishing order, the best position first.
+    ///
+    pub fn standings(&self) -> Vec<usize> {
+        let mut finished: Vec<usize> = (0..self.stacks.len()).filter(|p| self.positions[*p].is_some()).collect();
+        finished.sort_by_key(|p| self.positions[*p]);
+        finished
+    }
+
+    /// It returns the prize won by each player according to the [Payouts] of the given pool.
+    ///
+    pub fn prizes(&self, payouts: &Payouts, pool: u64) -> Vec<u64> {
+        let prizes = payouts.prizes(pool);
+        self.positions
+            .iter()
+            .map(|position| position.and_then(|p| prizes.get(p - 1)).copied().unwrap_or(0))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::{BlindSchedule, Move, Payouts, Tournament, TournamentError};
+    use crate::game::{Action, Betting, Blinds};
+
+    #[test]
+    fn tournament_schedule() {
+        let schedule = BlindSchedule::standard(10, 15, 6);
+        let smalls: Vec<u64> = schedule.levels.iter().map(|l| l.blinds.small).collect();
+        assert_eq!(smalls, vec![10, 20, 20, 30, 50, 80]);
+        assert_eq!(schedule.levels[3].blinds.ante, 7);
+        assert_eq!(schedule.level_at(0), 0);
+        assert_eq!(schedule.level_at(15), 1);
+        assert_eq!(schedule.level_at(1000), 5);
+        assert_eq!(
+            schedule.blinds_at(20),
+            Blinds {
+                small: 20,
+                big: 40,
+                ante: 0
+            }
+        );
+
+        assert_eq!(Payouts::top_three().prizes(1001), vec![501, 300, 200]);
+    }
+
+    #[test]
+    fn tournament_seating_and_balancing() {
+        let schedule = BlindSchedule::standard(10, 15, 6);
+        assert_eq!(
+            Tournament::new(1, 1000, 9, schedule.clone(), Betting::NoLimit).err(),
+            Some(TournamentError::InvalidPlayers)
+        );
+        let mut tournament = Tournament::new(21, 1000, 9, schedule, Betting::NoLimit).unwrap();
+        assert_eq!(tournament.tables.iter().map(|t| t.len()).collect::<Vec<_>>(), vec![7, 7, 7]);
+
+        // two players with the same stack bust at the first table, the first seat finishing last: the table is balanced
+        let mut stacks = vec![1000; 7];
+        stacks[0] = 0;
+        stacks[1] = 0;
+        stacks[2] = 3000;
+        let moves = tournament.record(0, &stacks);
+        assert_eq!(tournament.positions[0], Some(21));
+        assert_eq!(tournament.positions[3], Some(20));
+        // the player who would be the next big blind at the last of the longest tables moves
+        assert_eq!(moves, vec![Move { player: 8, from: 2, to: 0 }]);
+        assert_eq!(tournament.tables.iter().map(|t| t.len()).collect::<Vec<_>>(), vec![6, 7, 6]);
+
+        // down to 14 players, they fit in two tables: the shortest one is broken
+        let stacks: Vec<u64> = (0..7).map(|s| if s < 2 { 2000 } else { 0 }).collect();
+        let moves = tournament.record(1, &stacks);
+        assert_eq!(tournament.remaining().len(), 14);
+        assert_eq!(tournament.tables.iter().map(|t| t.len()).collect::<Vec<_>>(), vec![7, 7]);
+        assert_eq!(moves.len(), 2);
+        assert!(moves.iter().all(|m| m.from == 1));
+    }
+
+    #[test]
+    fn tournament_run() {
+        let schedule = BlindSchedule::standard(10, 10, 10);
+        let mut tournament = Tournament::new(12, 500, 6, schedule, Betting::NoLimit).unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+        // everybody shoves or calls: a quick tournament
+        let standings = tournament.run(&mut rng, 2, |game| match game.legal_actions() {
+            Some(legal) if legal.raise.is_some() => Action::AllIn,
+            Some(legal) if legal.call > 0 => Action::Call,
+            _ => Action::Check,
+        });
+        let standings = standings.unwrap();
+        assert_eq!(standings.len(), 12);
+        assert!(tournament.is_over());
+        assert_eq!(tournament.stacks[standings[0]], 6000);
+        assert_eq!(tournament.positions[standings[0]], Some(1));
+        assert_eq!(tournament.positions[standings[11]], Some(12));
+
+        let prizes = tournament.prizes(&Payouts::top_three(), 1200);
+        assert_eq!(prizes[standings[0]], 600);
+        assert_eq!(prizes[standings[2]], 240);
+        assert_eq!(prizes.iter().sum::<u64>(), 1200);
+    }
+}
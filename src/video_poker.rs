@@ -0,0 +1,354 @@
+use std::ops::RangeInclusive;
+
+use crate::card::{Card, Deck, Hand};
+use crate::match_evaluator::{MatchHandEvaluator, Rank, Score};
+use crate::wild::{WildEvaluator, WildRules};
+
+/// The [`PayLine`] struct. A line of a video poker paytable: the hands of the given [`Rank`], whose main cards have a value in `values`
+/// and with a number of wild cards in `wilds`, pay `pay` coins per coin bet.
+///
+/// The main cards are the ones making the rank, i.e. the pair of a [`Rank::OnePair`] or the four cards of a [`Rank::FourOfAKind`].
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct PayLine {
+    pub name: &'static str,
+    pub rank: Rank,
+    pub values: RangeInclusive<u8>,
+    pub wilds: RangeInclusive<usize>,
+    pub pay: u32,
+}
+
+impl PayLine {
+    /// A line paying any hand of the given [Rank].
+    ///
+    pub fn new(name: &'static str, rank: Rank, pay: u32) -> PayLine {
+        PayLine {
+            name,
+            rank,
+            values: 2..=14,
+            wilds: 0..=5,
+            pay,
+        }
+    }
+
+    /// It restricts the line to the hands whose main cards have a value in the range.
+    ///
+    pub fn values(self, values: RangeInclusive<u8>) -> PayLine {
+        PayLine { values, ..self }
+    }
+
+    /// It restricts the line to the hands with a number of wild cards in the range.
+    ///
+    pub fn wilds(self, wilds: RangeInclusive<usize>) -> PayLine {
+        PayLine { wilds, ..self }
+    }
+}
+
+/// The [`Paytable`] struct. It's a video poker game: the wild cards, if any, and the paytable for a coin bet, whose lines are checked in order.
+///
+/// The pays are the ones for a max bet of 5 coins, divided by 5: i.e. a Royal Flush pays 4000 coins for 5, so 800.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Paytable {
+    pub rules: WildRules,
+    pub lines: Vec<PayLine>,
+}
+
+/// The [`Hold`] struct. A choice of cards to hold out of the dealt hand, and the expected value of the draw in coins per coin bet.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Hold {
+    pub held: Vec<Card>,
+    pub ev: f64,
+}
+
+impl Paytable {
+    /// Jacks or Better, full pay 9/6 (99.54% return).
+    ///
+    pub fn jacks_or_better() -> Paytable {
+        Paytable {
+            rules: WildRules::default(),
+            lines: vec![
+                PayLine::new("Royal Flush", Rank::RoyalFlush, 800),
+                PayLine::new("Straight Flush", Rank::StraightFlush, 50),
+                PayLine::new("Four of a Kind", Rank::FourOfAKind, 25),
+                PayLine::new("Full House", Rank::FullHouse, 9),
+                PayLine::new("Flush", Rank::Flush, 6),
+                PayLine::new("Straight", Rank::Straight, 4),
+                PayLine::new("Three of a Kind", Rank::ThreeOfAKind, 3),
+                PayLine::new("Two Pair", Rank::TwoPair, 2),
+                PayLine::new("Jacks or Better", Rank::OnePair, 1).values(11..=14),
+            ],
+        }
+    }
+
+    /// Bonus Poker, 8/5 (99.17% return), with bonuses for four Aces and four 2s, 3s or 4s.
+    ///
+    pub fn bonus_poker() -> Paytable {
+        Paytable {
+            rules: WildRules::default(),
+            lines: vec![
+                PayLine::new("Royal Flush", Rank::RoyalFlush, 800),
+                PayLine::new("Straight Flush", Rank::StraightFlush, 50),
+                PayLine::new("Four Aces", Rank::FourOfAKind, 80).values(14..=14),
+                PayLine::new("Four 2s-4s", Rank::FourOfAKind, 40).values(2..=4),
+                PayLine::new("Four 5s-Ks", Rank::FourOfAKind, 25),
+                PayLine::new("Full House", Rank::FullHouse, 8),
+                PayLine::new("Flush", Rank::Flush, 5),
+                PayLine::new("Straight", Rank::Straight, 4),
+                PayLine::new("Three of a Kind", Rank::ThreeOfAKind, 3),
+                PayLine::new("Two Pair", Rank::TwoPair, 2),
+                PayLine::new("Jacks or Better", Rank::OnePair, 1).values(11..=14),
+            ],
+        }
+    }
+
+    /// Deuces Wild, full pay (100.76% return), where the four deuces are wild.
+    ///
+    pub fn deuces_wild() -> Paytable {
+        Paytable {
+            rules: WildRules::deuces_wild(),
+            lines: vec![
+                PayLine::new("Natural Royal Flush", Rank::RoyalFlush, 800).wilds(0..=0),
+                PayLine::new("Four Deuces", Rank::FiveOfAKind, 200).wilds(4..=4),
+                PayLine::new("Wild Royal Flush", Rank::RoyalFlush, 25),
+                PayLine::new("Five of a Kind", Rank::FiveOfAKind, 15),
+                PayLine::new("Straight Flush", Rank::StraightFlush, 9),
+                PayLine::new("Four of a Kind", Rank::FourOfAKind, 5),
+                PayLine::new("Full House", Rank::FullHouse, 3),
+                PayLine::new("Flush", Rank::Flush, 2),
+                PayLine::new("Straight", Rank::Straight, 2),
+                PayLine::new("Three of a Kind", Rank::ThreeOfAKind, 1),
+            ],
+        }
+    }
+
+    /// It returns the [PayLine] a final [Hand] is paid by, if any.
+    ///
+    pub fn line(&self, hand: &Hand) -> Option<&PayLine> {
+        let wilds = hand.get_hand_slice().iter().filter(|c| self.rules.is_wild(c)).count();
+        let score: Score = if wilds == 0 {
+            MatchHandEvaluator::score(&mut hand.clone())
+        } else {
+            WildEvaluator::score(hand, &self.rules)
+        };
+        self.lines.iter().find(|line| {
+            line.rank.position() == score.rank.position()
+                && line.values.contains(&score.kickers[0])
+                && line.wilds.contains(&wilds)
+        })
+    }
+
+    /// It returns the coins paid per coin bet by a final [Hand].
+    ///
+    pub fn pay(&self, hand: &Hand) -> u32 {
+        self.line(hand).map_or(0, |line| line.pay)
+    }
+
+    /// It computes the expected value of holding the `held` cards of the `dealt` [Hand], enumerating every draw from the remaining 47 cards.
+    ///
+    pub fn ev(&self, dealt: &Hand, held: &[Card]) -> f64 {
+        let remaining = Deck::create_ordered_deck().remaining(dealt.get_hand_slice());
+        let mut cards = held.to_vec();
+        let (mut total, mut count) = (0u64, 0u64);
+        for_each_combination(&remaining, 5 - held.len(), &mut cards, &mut |cards| {
+            total += self.pay(&Hand::new(cards.try_into().unwrap())) as u64;
+            count += 1;
+        });
+        total as f64 / count as f64
+    }
+
+    /// It computes the expected value of all the 32 holds of the `dealt` [Hand], sorted from the best to the worst.
+    ///
+    /// Every hold enumerates its draws, so this evaluates more than 1.7 million final hands.
+    ///
+    pub fn holds(&self, dealt: &Hand) -> Vec<Hold> {
+        let cards = dealt.get_hand_slice();
+        let mut holds: Vec<Hold> = (0..32u8)
+            .map(|mask| {
+                let held: Vec<Card> = (0..5).filter(|i| mask & (1 << i) != 0).map(|i| cards[i]).collect();
+                let ev = self.ev(dealt, &held);
+                Hold { held, ev }
+            })
+            .collect();
+        holds.sort_by(|a, b| b.ev.total_cmp(&a.ev));
+        holds
+    }
+
+    /// It returns the optimal [Hold] of the `dealt` [Hand], i.e. the one with the highest expected value.
+    ///
+    pub fn best_hold(&self, dealt: &Hand) -> Hold {
+        self.holds(dealt).swap_remove(0)
+    }
+
+    /// It computes the theoretical return of the game, playing the optimal hold on each of the 2,598,960 possible deals (1.0 being a 100% return).
+    ///
+    /// Solving the 32 holds of every deal one by one is out of reach, so the pays of the final hands are first totalled by every subset of
+    /// cards they contain: the pays of a hold are then the ones of the hands containing the held cards, less the ones containing any of
+    /// the discarded cards, by inclusion-exclusion.
+    ///
+    pub fn theoretical_return(&self) -> f64 {
+        let deck = Deck::create_ordered_deck().remaining(&[]);
+        let binomials = binomials();
+        // the total pay of the final hands containing each subset of cards, by size and combinatorial rank of the subset
+        let mut totals: Vec<Vec<u64>> = (0..=5).map(|k| vec![0; binomials[52][k] as usize]).collect();
+        for_each_deal(|hand| {
+            let pay = self.pay(&Hand::new(hand.map(|i| deck[i]))) as u64;
+            for (k, rank) in subsets(&hand, &binomials) {
+                totals[k][rank] += pay;
+            }
+        });
+
+        let mut total = 0.0;
+        for_each_deal(|dealt| {
+            let mut pays = subsets(&dealt, &binomials).map(|(k, rank)| totals[k][rank] as i64);
+            // every hold keeps the draws without any of its discarded cards
+            for card in 0..5 {
+                for held in 0..32 {
+                    if held & (1 << card) == 0 {
+                        pays[held] -= pays[held | (1 << card)];
+                    }
+                }
+            }
+            total += (0..32)
+                .map(|held: usize| pays[held] as f64 / binomials[47][5 - held.count_ones() as usize] as f64)
+                .fold(0.0, f64::max);
+        });
+        total / binomials[52][5] as f64
+    }
+}
+
+/// function that tabulates the binomial coefficients C(n, k), for n up to 52 and k up to 5
+///
+fn binomials() -> [[u64; 6]; 53] {
+    let mut binomials = [[0; 6]; 53];
+    for n in 0..=52 {
+        binomials[n][0] = 1;
+        for k in 1..=5.min(n) {
+            binomials[n][k] = binomials[n - 1][k - 1] + binomials[n - 1][k];
+        }
+    }
+    binomials
+}
+
+/// function that calls `f` with the indexes, in increasing order, of the cards of every 5 card hand out of 52
+///
+fn for_each_deal(mut f: impl FnMut([usize; 5])) {
+    for a in 0..52 {
+        for b in a + 1..52 {
+            for c in b + 1..52 {
+                for d in c + 1..52 {
+                    for e in d + 1..52 {
+                        f([a, b, c, d, e]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// function that lists the size and the combinatorial rank of the 32 subsets of a hand, the subset of the `i`-th card having bit `i` set
+///
+fn subsets(hand: &[usize; 5], binomials: &[[u64; 6]; 53]) -> [(usize, usize); 32] {
+    let mut subsets = [(0, 0); 32];
+    for mask in 1..32usize {
+        // the highest card of the subset comes last, after the subset of the lower cards
+        let last = mask.ilog2() as usize;
+        let (size, rank) = subsets[mask ^ (1 << last)];
+        subsets[mask] = (size + 1, rank + binomials[hand[last]][size + 1] as usize);
+    }
+    subsets
+}
+
+/// function that calls `f` with the `prefix` cards plus every combination of `k` of the given cards
+///
+fn for_each_combination(cards: &[Card], k: usize, prefix: &mut Vec<Card>, f: &mut impl FnMut(&[Card])) {
+    if k == 0 {
+        f(prefix);
+        return;
+    }
+    for i in 0..cards.len() {
+        prefix.push(cards[i]);
+        for_each_combination(&cards[i + 1..], k - 1, prefix, f);
+        prefix.pop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Paytable;
+    use crate::card::{Card, Hand};
+    use crate::{hand, newcard};
+
+    #[test]
+    fn video_poker_jacks_or_better_pays() {
+        let table = Paytable::jacks_or_better();
+        assert_eq!(table.pay(&hand!["Ah", "Kh", "Qh", "Jh", "10h"]), 800);
+        assert_eq!(table.pay(&hand!["Jh", "Jd", "Qh", "5c", "10h"]), 1);
+        assert_eq!(table.pay(&hand!["10h", "10d", "Qh", "5c", "3h"]), 0);
+        assert_eq!(table.pay(&hand!["10h", "10d", "5h", "5c", "3h"]), 2);
+        assert_eq!(table.pay(&hand!["Ah", "Kd", "Qh", "5c", "3h"]), 0);
+        assert_eq!(table.line(&hand!["Ah", "Ad", "Ac", "As", "3h"]).unwrap().name, "Four of a Kind");
+    }
+
+    #[test]
+    fn video_poker_bonus_pays() {
+        let table = Paytable::bonus_poker();
+        assert_eq!(table.pay(&hand!["Ah", "Ad", "Ac", "As", "3h"]), 80);
+        assert_eq!(table.pay(&hand!["3h", "3d", "3c", "3s", "Ah"]), 40);
+        assert_eq!(table.pay(&hand!["Kh", "Kd", "Kc", "Ks", "Ah"]), 25);
+        assert_eq!(table.pay(&hand!["Kh", "Kd", "Kc", "5s", "5h"]), 8);
+    }
+
+    #[test]
+    fn video_poker_deuces_wild_pays() {
+        let table = Paytable::deuces_wild();
+        assert_eq!(table.pay(&hand!["Ah", "Kh", "Qh", "Jh", "10h"]), 800);
+        assert_eq!(table.pay(&hand!["2h", "Kh", "Qh", "Jh", "10h"]), 25);
+        assert_eq!(table.pay(&hand!["2h", "2d", "2c", "2s", "10h"]), 200);
+        assert_eq!(table.pay(&hand!["2h", "2d", "10c", "10s", "10h"]), 15);
+        assert_eq!(table.pay(&hand!["2h", "9h", "8h", "7h", "5h"]), 9);
+        assert_eq!(table.pay(&hand!["2h", "Kd", "Kc", "5s", "4h"]), 1);
+        assert_eq!(table.pay(&hand!["Ah", "Ad", "Kc", "5s", "4h"]), 0);
+    }
+
+    #[test]
+    fn video_poker_ev() {
+        let table = Paytable::jacks_or_better();
+        let dealt = hand!["Ah", "Kh", "Qh", "Jh", "2c"];
+        // 1 royal, 8 flushes, 3 straights and 12 high pairs out of 47 draws
+        let held = [newcard!["Ah"], newcard!["Kh"], newcard!["Qh"], newcard!["Jh"]];
+        assert!((table.ev(&dealt, &held) - 872.0 / 47.0).abs() < 1e-9);
+        assert_eq!(table.ev(&dealt, dealt.get_hand_slice()), 0.0);
+    }
+
+    #[test]
+    fn video_poker_theoretical_return() {
+        let jacks = Paytable::jacks_or_better().theoretical_return();
+        assert!((jacks - 0.995439).abs() < 1e-6, "{jacks}");
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, ignore = "too slow without optimizations, run with cargo test --release")]
+    fn video_poker_theoretical_return_bonus_and_deuces() {
+        let bonus = Paytable::bonus_poker().theoretical_return();
+        assert!((bonus - 0.991660).abs() < 1e-6, "{bonus}");
+        let deuces = Paytable::deuces_wild().theoretical_return();
+        assert!((deuces - 1.007620).abs() < 1e-6, "{deuces}");
+    }
+
+    #[test]
+    fn video_poker_best_hold() {
+        let table = Paytable::jacks_or_better();
+        let dealt = hand!["Ah", "Kh", "Qh", "Jh", "Jc"];
+        let holds = table.holds(&dealt);
+        assert_eq!(holds.len(), 32);
+        // breaking the high pair for the royal draw
+        assert_eq!(holds[0].held, vec![newcard!["Ah"], newcard!["Kh"], newcard!["Qh"], newcard!["Jh"]]);
+        assert!(holds.windows(2).all(|w| w[0].ev >= w[1].ev));
+
+        // a dealt straight flush is kept as it is
+        let pat: Hand = hand!["9c", "8c", "7c", "6c", "5c"];
+        assert_eq!(table.ev(&pat, pat.get_hand_slice()), 50.0);
+    }
+}
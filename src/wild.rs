@@ -0,0 +1,291 @@
+use strum::IntoEnumIterator;
+
+use crate::card::{Card, Hand, Suit};
+use crate::match_evaluator::{MatchHandEvaluator, Rank, Score};
+
+/// The [`WildRules`] struct. It tells which cards are wild, i.e. can stand for any card not already in the hand.
+///
+/// Jokers are always wild. When `bug` is set, a joker is a "bug": it can only stand for an Ace, or complete a straight, a flush or a straight flush.
+/// The cards whose value is among `wild_values` are fully wild, i.e. `vec![2]` for Deuces Wild.
+///
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct WildRules {
+    pub wild_values: Vec<u8>,
+    pub bug: bool,
+}
+
+impl WildRules {
+    /// Fully wild jokers, and no other wild card.
+    ///
+    pub fn jokers() -> WildRules {
+        WildRules::default()
+    }
+
+    /// Jokers playing as the bug, as in Pai Gow poker.
+    ///
+    pub fn bug() -> WildRules {
+        WildRules {
+            wild_values: vec![],
+            bug: true,
+        }
+    }
+
+    /// Deuces Wild, the four deuces (and the jokers, if any) being fully wild.
+    ///
+    pub fn deuces_wild() -> WildRules {
+        WildRules {
+            wild_values: vec![2],
+            bug: false,
+        }
+    }
+
+    /// It tells if a card is wild under these rules.
+    ///
+    pub fn is_wild(&self, card: &Card) -> bool {
+        card.is_joker() || self.wild_values.contains(&card.val)
+    }
+}
+
+/// The [`WildEvaluator`] struct. It evaluates hands containing wild cards, by finding the best natural card each wild card stands for.
+///
+/// i.e. A♥️ A♦️ A♣️ A♠️ 🃏 makes [`Rank::FiveOfAKind`], the best possible hand: the only one where a wild card duplicates a card of the hand.
+///
+pub struct WildEvaluator;
+
+impl WildEvaluator {
+    /// It evaluates the best [Score] of a [Hand] containing wild cards.
+    ///
+    pub fn score(hand: &Hand, rules: &WildRules) -> Score {
+        Self::best(hand.get_hand_slice(), rules).1
+    }
+
+    /// It returns the natural [Hand] the wild cards stand for, making the best [Score], sorted in descending order.
+    ///
+    pub fn best_substitute(hand: &Hand, rules: &WildRules) -> Hand {
+        Self::best(hand.get_hand_slice(), rules).0
+    }
+
+    /// It evaluates the best [Score] that can be made with any 5 of the given cards, wild cards included.
+    ///
+    /// # Panics
+    ///
+    /// If less than 5 cards are given.
+    ///
+    pub fn best_score(cards: &[Card], rules: &WildRules) -> Score {
+        assert!(cards.len() >= 5, "at least 5 cards are needed to make a hand");
+        let n = cards.len();
+        let mut best: Option<Score> = None;
+        for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    for d in c + 1..n {
+                        for e in d + 1..n {
+                            let hand = [cards[a], cards[b], cards[c], cards[d], cards[e]];
+                            let score = Self::best(&hand, rules).1;
+                            if best.is_none_or(|best| score < best) {
+                                best = Some(score);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        best.unwrap()
+    }
+
+    /// function that tries every substitution of the wild cards and keeps the best natural hand
+    ///
+    fn best(hand: &[Card; 5], rules: &WildRules) -> (Hand, Score) {
+        let naturals: Vec<Card> = hand.iter().filter(|c| !rules.is_wild(c)).copied().collect();
+        let wilds: Vec<Card> = hand.iter().filter(|c| rules.is_wild(c)).copied().collect();
+        // whether each wild card is a bug, the bugs last
+        let mut bugs: Vec<bool> = wilds.iter().map(|c| rules.bug && c.is_joker()).collect();
+        bugs.sort();
+
+        // nothing beats five of a kind, when every wild card can take the value of the naturals
+        let value = naturals.first().map_or(14, |c| c.val);
+        if naturals.iter().all(|c| c.val == value) && (value == 14 || !bugs.contains(&true)) {
+            let mut five = naturals.clone();
+            five.extend(Suit::iter().cycle().take(wilds.len()).map(|suit| Card::new(value, suit)));
+            return Self::evaluate(&five).unwrap();
+        }
+
+        // suits only matter for flushes, which need all the naturals to be of the same suit: a single suit is enough
+        let suit = naturals[0].suit;
+        let substitutes: Vec<u8> = (2..=14).collect();
+        let mut best: Option<(Hand, Score)> = None;
+        let mut picks = vec![0; wilds.len()];
+        loop {
+            let mut cards = naturals.clone();
+            for i in &picks {
+                cards.extend(Self::substitute(&cards, substitutes[*i], suit));
+            }
+            if let Some((hand, score)) = Self::evaluate(&cards) {
+                let valid = bugs.iter().zip(&picks).all(|(bug, i)| {
+                    !bug || substitutes[*i] == 14
+                        || matches!(
+                            score.rank,
+                            Rank::Straight | Rank::Flush | Rank::StraightFlush | Rank::RoyalFlush
+                        )
+                });
+                if valid && best.as_ref().is_none_or(|(_, best)| score < *best) {
+                    best = Some((hand, score));
+                }
+            }
+            if !Self::advance(&mut picks, &bugs, substitutes.len()) {
+                break;
+            }
+        }
+        best.unwrap()
+    }
+
+    /// function that picks the card of the given value a wild card stands for: of the given suit when it isn't already among the cards,
+    /// else of another suit, as a wild card can't duplicate a card (i.e. a joker with A♥️ 9♥️ 7♥️ 3♥️ is the K♥️ flush, not a second A♥️)
+    ///
+    fn substitute(cards: &[Card], val: u8, suit: Suit) -> Option<Card> {
+        std::iter::once(suit)
+            .chain(Suit::iter())
+            .map(|suit| Card::new(val, suit))
+            .find(|card| !cards.contains(card))
+    }
+
+    /// function that moves to the next substitution, wild cards of the same kind being interchangeable
+    ///
+    fn advance(picks: &mut [usize], bugs: &[bool], n: usize) -> bool {
+        for i in (0..picks.len()).rev() {
+            if picks[i] + 1 < n {
+                picks[i] += 1;
+                for j in i + 1..picks.len() {
+                    // the substitutions of wild cards of the same kind are kept in non decreasing order
+                    picks[j] = if bugs[j] == bugs[i] { picks[i] } else { 0 };
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    /// function that scores five natural cards
+    ///
+    fn evaluate(cards: &[Card]) -> Option<(Hand, Score)> {
+        let mut hand = Hand::new(cards.try_into().ok()?);
+        let score = MatchHandEvaluator::score(&mut hand);
+        Some((hand, score))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{WildEvaluator, WildRules};
+    use crate::card::{Card, Hand, Suit};
+    use crate::match_evaluator::{MatchHandEvaluator, Rank};
+    use crate::{hand, newcard};
+
+    fn joker() -> Card {
+        Card::joker(Suit::Spades)
+    }
+
+    fn wild_hand(cards: [&str; 4]) -> Hand {
+        let [a, b, c, d] = cards.map(|c| newcard![c]);
+        Hand::new([a, b, c, d, joker()])
+    }
+
+    #[test]
+    fn wild_five_of_a_kind() {
+        let hand = wild_hand(["Ah", "Ad", "Ac", "As"]);
+        let score = WildEvaluator::score(&hand, &WildRules::jokers());
+        assert_eq!(score.rank, Rank::FiveOfAKind);
+        assert_eq!(score.kickers, [14; 5]);
+        assert_eq!(WildEvaluator::score(&hand, &WildRules::bug()).rank, Rank::FiveOfAKind);
+
+        // five of a kind beats a natural royal flush
+        let royal = MatchHandEvaluator::score(&mut hand!["Ah", "Kh", "Qh", "Jh", "10h"]);
+        assert!(score < royal);
+
+        // the bug can't make five kings
+        let hand = wild_hand(["Kh", "Kd", "Kc", "Ks"]);
+        assert_eq!(WildEvaluator::score(&hand, &WildRules::jokers()).rank, Rank::FiveOfAKind);
+        let bug = WildEvaluator::score(&hand, &WildRules::bug());
+        assert_eq!(bug.rank, Rank::FourOfAKind);
+        assert_eq!(bug.kickers, [13, 13, 13, 13, 14]);
+    }
+
+    #[test]
+    fn wild_best_substitute() {
+        let rules = WildRules::jokers();
+        let hand = wild_hand(["Kh", "Qh", "Jh", "10h"]);
+        assert_eq!(WildEvaluator::best_substitute(&hand, &rules), hand!["Ah", "Kh", "Qh", "Jh", "10h"]);
+        assert_eq!(WildEvaluator::score(&hand, &rules).rank, Rank::RoyalFlush);
+
+        let hand = wild_hand(["Kh", "Kd", "5c", "5s"]);
+        let score = WildEvaluator::score(&hand, &rules);
+        assert_eq!(score.rank, Rank::FullHouse);
+        assert_eq!(score.kickers, [13, 13, 13, 5, 5]);
+
+        // the joker can't be a second A♥️: it's the highest missing heart, and a natural A-K flush is as good
+        let hand = wild_hand(["Ah", "9h", "7h", "3h"]);
+        assert_eq!(WildEvaluator::best_substitute(&hand, &rules), hand!["Ah", "Kh", "9h", "7h", "3h"]);
+        let natural = MatchHandEvaluator::score(&mut hand!["Ad", "Kd", "9d", "7d", "3d"]);
+        assert_eq!(WildEvaluator::score(&hand, &rules), natural);
+        assert!(MatchHandEvaluator::score(&mut hand!["Ad", "Kd", "10d", "7d", "3d"]) < natural);
+        for rules in [WildRules::bug(), WildRules::deuces_wild()] {
+            assert_eq!(WildEvaluator::score(&hand, &rules).kickers, [14, 13, 9, 7, 3]);
+        }
+    }
+
+    #[test]
+    fn wild_bug() {
+        let rules = WildRules::bug();
+        // the bug completes straights and flushes
+        assert_eq!(WildEvaluator::score(&wild_hand(["9c", "8d", "7h", "6s"]), &rules).rank, Rank::Straight);
+        assert_eq!(WildEvaluator::score(&wild_hand(["9c", "8c", "3c", "2c"]), &rules).rank, Rank::Flush);
+        assert_eq!(WildEvaluator::score(&wild_hand(["9c", "8c", "7c", "6c"]), &rules).rank, Rank::StraightFlush);
+
+        // otherwise it's an Ace
+        let score = WildEvaluator::score(&wild_hand(["Kc", "Kd", "7h", "2s"]), &rules);
+        assert_eq!(score.rank, Rank::OnePair);
+        assert_eq!(score.kickers, [13, 13, 14, 7, 2]);
+        let score = WildEvaluator::score(&wild_hand(["Ac", "Kd", "7h", "2s"]), &rules);
+        assert_eq!(score.rank, Rank::OnePair);
+        assert_eq!(score.kickers, [14, 14, 13, 7, 2]);
+
+        // a fully wild joker makes trips instead
+        let score = WildEvaluator::score(&wild_hand(["Kc", "Kd", "7h", "2s"]), &WildRules::jokers());
+        assert_eq!(score.rank, Rank::ThreeOfAKind);
+    }
+
+    #[test]
+    fn wild_deuces() {
+        let rules = WildRules::deuces_wild();
+        let score = WildEvaluator::score(&hand!["2h", "2d", "Kc", "Kh", "9s"], &rules);
+        assert_eq!(score.rank, Rank::FourOfAKind);
+        assert_eq!(score.kickers, [13, 13, 13, 13, 9]);
+
+        let score = WildEvaluator::score(&hand!["2h", "2d", "Kc", "Qc", "10c"], &rules);
+        assert_eq!(score.rank, Rank::RoyalFlush);
+
+        // four deuces and a joker
+        let hand = Hand::new([newcard!["2h"], newcard!["2d"], newcard!["2c"], newcard!["2s"], joker()]);
+        let score = WildEvaluator::score(&hand, &rules);
+        assert_eq!(score.rank, Rank::FiveOfAKind);
+        assert_eq!(score.kickers, [14; 5]);
+
+        // no wild card at all
+        assert_eq!(WildEvaluator::score(&hand!["Kh", "Kd", "Qc", "Qs", "9c"], &rules).rank, Rank::TwoPair);
+    }
+
+    #[test]
+    fn wild_best_of_seven() {
+        let cards = [
+            newcard!["Ah"],
+            newcard!["Kd"],
+            newcard!["Qc"],
+            newcard!["Jh"],
+            newcard!["3s"],
+            newcard!["3d"],
+            joker(),
+        ];
+        assert_eq!(WildEvaluator::best_score(&cards, &WildRules::bug()).rank, Rank::Straight);
+        assert_eq!(WildEvaluator::best_score(&cards, &WildRules::jokers()).rank, Rank::Straight);
+    }
+}